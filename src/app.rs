@@ -1,8 +1,31 @@
 use crate::modules::{
-    geometry, joint::BoltedJoint, library::Library, state::UIState, utils::text_width,
+    analysis::{self, LoadCase, Tightening},
+    calc::Case,
+    eccentricity::Eccentricity,
+    elements::{Bolt, HeadType, Nut, Washer, WasherType},
+    embedding::LoadDirection,
+    fastener_catalogue, fatigue,
+    geometry::Point,
+    joint::BoltedJoint,
+    library::{Library, PartId},
+    library_file,
+    library_merge::{self, Change, Diff, JointChange, Resolution},
+    pattern::{self, BoltPattern, Distribution, Layout, PatternLoads},
+    property_class,
+    state::{FileAction, SweepEntries, UIState},
+    sweep::{self, Sweep, SweepError, SweepReport, Values},
+    thermal::Temperatures,
+    thread::{ThreadForm, Unit},
+    thread_catalogue,
+    torque::{self, TighteningMethod},
+    utils::State,
+    washer_catalogue,
 };
-use egui::{vec2, Frame, Rounding, Stroke, Vec2};
-use egui_flex::{item, Flex, FlexAlign, FlexAlignContent, FlexDirection, FlexItem};
+use std::path::Path;
+use std::sync::mpsc;
+
+use egui::{Frame, Rounding, Stroke, Vec2, vec2};
+use egui_flex::{Flex, FlexAlign, FlexAlignContent, FlexDirection, FlexItem, item};
 use hello_egui_utils::center::Center;
 
 /// We derive Deserialize/Serialize so we can persist app state on shutdown.
@@ -11,7 +34,25 @@ use hello_egui_utils::center::Center;
 pub struct Studio {
     joint: BoltedJoint,
     library: Library,
+    tightening: Tightening,
+    load: LoadCase,
+    pattern: Option<BoltPattern>, // None analyses the joint as a single bolt
+    pattern_loads: PatternLoads,
     state: UIState,
+    settings: State,
+    #[serde(skip)]
+    merging: Option<Merging>,
+    #[serde(skip)]
+    sweep: Option<Result<SweepReport, String>>, // last sweep run from the card
+    #[serde(skip)]
+    sweeping: Option<mpsc::Receiver<Result<SweepReport, String>>>, // sweep still running
+}
+
+/// Incoming library being merged, conflicts resolved one at a time
+struct Merging {
+    theirs: Library,
+    diff: Diff,
+    current: usize, // index of the conflict on show
 }
 
 impl Default for Studio {
@@ -19,7 +60,15 @@ impl Default for Studio {
         Self {
             joint: BoltedJoint::default(),
             library: Library::default(),
+            tightening: Tightening::default(),
+            load: LoadCase::default(),
+            pattern: None,
+            pattern_loads: PatternLoads::default(),
             state: UIState::default(),
+            settings: State::default(),
+            merging: None,
+            sweep: None,
+            sweeping: None,
         }
     }
 }
@@ -72,7 +121,16 @@ impl Studio {
         // Load previous app state (if any).
         // Note that you must enable the `persistence` feature for this to work.
         if let Some(storage) = cc.storage {
-            return eframe::get_value(storage, eframe::APP_KEY).unwrap_or_default();
+            if let Some(studio) = eframe::get_value(storage, eframe::APP_KEY) {
+                return studio;
+            }
+            // State saved before the library held part keys no longer reads
+            if storage.get_string(eframe::APP_KEY).is_some() {
+                log::warn!(
+                    "Saved state could not be read and was reset, save the library to a file \
+                     with an older release and load it here to keep it"
+                );
+            }
         }
 
         Default::default()
@@ -94,17 +152,32 @@ impl Studio {
         if self.state.show_settings {
             self.show_settings_window(ctx);
         }
+        if let Some(action) = self.state.file_action {
+            self.show_file_window(ctx, action);
+        }
+        if self.merging.is_some() {
+            self.show_merge_window(ctx);
+        }
     }
 
     fn show_main_menu(&mut self, ui: &mut egui::Ui) {
         egui::MenuBar::new().ui(ui, |ui| {
             ui.menu_button("File", |ui| {
                 if ui.button("New").clicked() {
-                    // Handle new file
+                    self.library = Library::default();
+                    self.joint = BoltedJoint::default();
                     ui.close();
                 }
                 if ui.button("Open").clicked() {
-                    // Handle open file
+                    self.state.file_action = Some(FileAction::Open);
+                    ui.close();
+                }
+                if ui.button("Save").clicked() {
+                    self.state.file_action = Some(FileAction::Save);
+                    ui.close();
+                }
+                if ui.button("Merge").clicked() {
+                    self.state.file_action = Some(FileAction::Merge);
                     ui.close();
                 }
                 ui.separator();
@@ -182,6 +255,19 @@ impl Studio {
                                         cols[1].label("Length:");
                                         cols[1].add(egui::Slider::new(&mut 50.0, 20.0..=200.0));
                                     });
+                                    ui.horizontal(|ui| {
+                                        ui.label("Thread:");
+                                        ui.add(
+                                            egui::TextEdit::singleline(
+                                                &mut self.state.thread_designation,
+                                            )
+                                            .desired_width(100.0),
+                                        );
+                                        Self::show_thread_summary(
+                                            ui,
+                                            &self.state.thread_designation,
+                                        );
+                                    });
                                     ui.horizontal(|ui| {
                                         ui.label("Grade:");
                                         egui::ComboBox::new("grade", "")
@@ -193,6 +279,8 @@ impl Studio {
                                                 );
                                             });
                                     });
+                                    self.show_fasteners(ui);
+                                    self.show_washers(ui);
                                 });
                             });
 
@@ -225,17 +313,7 @@ impl Studio {
                             // Analysis card (1x1)
                             ui.allocate_ui(grid.card_size(1, 1), |ui| {
                                 Self::sized_card(ui, "Analysis", "📊", |ui| {
-                                    ui.label("Results:");
-                                    ui.horizontal(|ui| {
-                                        ui.label("Stress:");
-                                        ui.label(egui::RichText::new("245 MPa").strong());
-                                    });
-                                    ui.horizontal(|ui| {
-                                        ui.label("Factor:");
-                                        ui.label(
-                                            egui::RichText::new("2.8").color(egui::Color32::GREEN),
-                                        );
-                                    });
+                                    self.show_analysis(ui);
                                 });
                             });
 
@@ -246,9 +324,53 @@ impl Studio {
                                 Self::sized_card(ui, "Calculator", "🔢", |ui| {
                                     ui.horizontal(|ui| {
                                         ui.label("Load:");
-                                        ui.add(egui::DragValue::new(&mut 1000).suffix(" N"));
+                                        ui.add(
+                                            egui::DragValue::new(&mut self.load.axial_min)
+                                                .range(0.0..=self.load.axial_max)
+                                                .suffix(" N"),
+                                        )
+                                        .on_hover_text("F_Amin");
+                                        ui.add(
+                                            egui::DragValue::new(&mut self.load.axial_max)
+                                                .range(0.0..=f64::MAX)
+                                                .suffix(" N"),
+                                        )
+                                        .on_hover_text("F_Amax");
+                                    });
+                                    self.show_cycles(ui);
+                                    self.show_transverse_load(ui);
+                                    self.show_eccentricity(ui);
+                                    ui.horizontal(|ui| {
+                                        ui.label("αA:");
+                                        ui.add(
+                                            egui::DragValue::new(&mut self.tightening.alpha_a)
+                                                .range(1.0..=4.0)
+                                                .speed(0.05),
+                                        );
                                     });
-                                    ui.label("Preload: 2800 N");
+                                    ui.horizontal(|ui| {
+                                        ui.label("Rz:");
+                                        ui.add(
+                                            egui::DragValue::new(&mut self.load.roughness)
+                                                .range(0.0..=160.0)
+                                                .suffix(" μm"),
+                                        );
+                                        ui.selectable_value(
+                                            &mut self.load.load_direction,
+                                            LoadDirection::Axial,
+                                            "Axial",
+                                        );
+                                        ui.selectable_value(
+                                            &mut self.load.load_direction,
+                                            LoadDirection::Transverse,
+                                            "Transverse",
+                                        );
+                                    });
+                                    self.show_tightening_method(ui);
+                                    self.show_thermal_settings(ui);
+                                    if ui.small_button("Copy torque table").clicked() {
+                                        self.copy_torque_table(ui.ctx());
+                                    }
                                 });
                             });
 
@@ -258,9 +380,30 @@ impl Studio {
                             ui.allocate_ui(grid.card_size(1, 1), |ui| {
                                 Self::sized_card(ui, "Materials", "📚", |ui| {
                                     ui.label("Standards:");
-                                    if ui.small_button("ISO 8.8").clicked() {}
-                                    if ui.small_button("ISO 10.9").clicked() {}
-                                    if ui.small_button("ASTM").clicked() {}
+                                    if ui.small_button("ISO 8.8").clicked() {
+                                        self.apply_bolt_class("8.8");
+                                    }
+                                    if ui.small_button("ISO 10.9").clicked() {
+                                        self.apply_bolt_class("10.9");
+                                    }
+                                    if ui.small_button("ASTM").clicked() {
+                                        self.apply_bolt_class("B7");
+                                    }
+                                    ui.menu_button("Other classes", |ui| {
+                                        let mut shown = Vec::new();
+                                        for class in property_class::bolt_classes() {
+                                            if shown.contains(&class.designation) {
+                                                continue;
+                                            }
+                                            shown.push(class.designation);
+                                            let label =
+                                                format!("{} {}", class.standard, class.designation);
+                                            if ui.button(label).clicked() {
+                                                self.apply_bolt_class(class.designation);
+                                                ui.close();
+                                            }
+                                        }
+                                    });
                                 });
                             });
 
@@ -278,6 +421,27 @@ impl Studio {
 
                         ui.add_space(gap);
 
+                        // Pattern row
+                        ui.horizontal(|ui| {
+                            // Bolt pattern card (2x2)
+                            ui.allocate_ui(grid.card_size(2, 2), |ui| {
+                                Self::sized_card(ui, "Bolt Pattern", "⭘", |ui| {
+                                    self.show_pattern(ui);
+                                });
+                            });
+
+                            ui.add_space(gap);
+
+                            // Parameter sweep card (2x2)
+                            ui.allocate_ui(grid.card_size(2, 2), |ui| {
+                                Self::sized_card(ui, "Parameter Sweep", "🔀", |ui| {
+                                    self.show_sweep(ui);
+                                });
+                            });
+                        });
+
+                        ui.add_space(gap);
+
                         // Row 3 - Example of tall card
                         ui.horizontal(|ui| {
                             // Settings card (1x2) - tall card
@@ -321,6 +485,1129 @@ impl Studio {
             });
     }
 
+    /// Gives the joint's bolt or stud the properties of a class, creating a bolt from the
+    /// thread designation if the joint has no fastener, and pairs the nut to match
+    fn apply_bolt_class(&mut self, designation: &str) {
+        let has_bolt = self
+            .joint
+            .bolt_id
+            .and_then(|id| self.library.bolt(id))
+            .is_some();
+        let has_stud = self
+            .joint
+            .stud_id
+            .and_then(|id| self.library.stud(id))
+            .is_some();
+        if !has_bolt && !has_stud {
+            let thread =
+                thread_catalogue::parse(&self.state.thread_designation).unwrap_or_default();
+            self.joint.bolt_id = Some(self.library.insert(
+                String::new(),
+                Bolt {
+                    thread,
+                    ..Default::default()
+                },
+            ));
+            self.joint.stud_id = None;
+        }
+        if let Err(error) = sweep::assign_class(&mut self.library, &self.joint, designation) {
+            log::warn!("{error}");
+        }
+    }
+
+    fn show_fasteners(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Bolt:");
+            ui.add(
+                egui::TextEdit::singleline(&mut self.state.bolt_designation).desired_width(160.0),
+            );
+            if ui.button("Apply").clicked() {
+                match fastener_catalogue::bolt(&self.state.bolt_designation) {
+                    Ok(bolt) => {
+                        self.apply_bolt(bolt);
+                        if let Some(id) = self.joint.bolt_id {
+                            self.library.rename(id, &self.state.bolt_designation);
+                        }
+                    }
+                    Err(error) => log::warn!("{error}"),
+                }
+            }
+            if let Some(bolt) = self.joint.bolt_id.and_then(|id| self.library.bolt(id)) {
+                ui.label(format!(
+                    "{}, k = {} mm, d_w = {} mm",
+                    bolt.head_type.name(),
+                    bolt.head_thickness,
+                    bolt.bearing_od
+                ));
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label("Nut:");
+            ui.add(
+                egui::TextEdit::singleline(&mut self.state.nut_designation).desired_width(160.0),
+            );
+            if ui.button("Apply").clicked() {
+                match fastener_catalogue::nut(&self.state.nut_designation) {
+                    Ok(nut) => {
+                        self.apply_nut(nut);
+                        if let Some(id) = self.joint.nut_id {
+                            self.library.rename(id, &self.state.nut_designation);
+                        }
+                    }
+                    Err(error) => log::warn!("{error}"),
+                }
+            }
+        });
+    }
+
+    /// Replaces the dimensions of the joint's bolt with catalogued ones, keeping its
+    /// material and thread rolling
+    fn apply_bolt(&mut self, bolt: Bolt) {
+        match self
+            .joint
+            .bolt_id
+            .and_then(|id| self.library.part_mut::<Bolt>(id))
+        {
+            Some(current) => {
+                *current = Bolt {
+                    rolling: current.rolling,
+                    material: current.material.clone(),
+                    ..bolt
+                }
+            }
+            None => self.joint.bolt_id = Some(self.library.insert(String::new(), bolt)),
+        }
+    }
+
+    /// Replaces the dimensions of the joint's nut with catalogued ones, keeping its
+    /// material, prevailing torque and drive
+    fn apply_nut(&mut self, nut: Nut) {
+        match self
+            .joint
+            .nut_id
+            .and_then(|id| self.library.part_mut::<Nut>(id))
+        {
+            Some(current) => {
+                *current = Nut {
+                    prev_trq: current.prev_trq,
+                    mass_on: current.mass_on,
+                    drive: current.drive.clone(),
+                    material: current.material.clone(),
+                    ..nut
+                }
+            }
+            None => self.joint.nut_id = Some(self.library.insert(String::new(), nut)),
+        }
+    }
+
+    fn show_washers(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Washers:");
+            let selected = self
+                .joint
+                .head_washer_id
+                .and_then(|id| self.library.washer(id))
+                .map_or("None", |w| w.kind.name());
+            let mut choice = None;
+            egui::ComboBox::from_id_salt("washers")
+                .selected_text(selected)
+                .show_ui(ui, |ui| {
+                    if ui.selectable_label(false, "None").clicked() {
+                        choice = Some(None);
+                    }
+                    for kind in [
+                        WasherType::Plain,
+                        WasherType::Hardened,
+                        WasherType::WedgeLock,
+                    ] {
+                        if ui.selectable_label(false, kind.name()).clicked() {
+                            choice = Some(Some(kind));
+                        }
+                    }
+                });
+            if let Some(kind) = choice {
+                self.apply_washers(kind);
+            }
+        });
+    }
+
+    /// Puts standard washers for the thread designation under the head and, for a
+    /// through bolt, under the nut
+    ///
+    /// The joint's previous washer entries are overwritten with the new washers and any
+    /// left over are removed. Entries a stored joint still uses are left alone.
+    fn apply_washers(&mut self, kind: Option<WasherType>) {
+        let stored = |id: PartId| {
+            self.library
+                .joints()
+                .iter()
+                .any(|joint| joint.references().any(|r| r == id))
+        };
+        let mut spare: Vec<PartId> = [self.joint.head_washer_id, self.joint.nut_washer_id]
+            .into_iter()
+            .flatten()
+            .filter(|&id| self.library.washer(id).is_some() && !stored(id))
+            .collect();
+        spare.dedup();
+        self.joint.head_washer_id = None;
+        self.joint.nut_washer_id = None;
+
+        if let Some(washer) = kind.and_then(|kind| self.standard_washer(kind)) {
+            let mut place = |library: &mut Library, washer: Washer| match spare.pop() {
+                Some(id) => {
+                    if let Some(stored) = library.part_mut::<Washer>(id) {
+                        *stored = washer;
+                    }
+                    id
+                }
+                None => library.insert(String::new(), washer),
+            };
+            // A countersunk head sits in its countersink without a washer
+            let countersunk = self
+                .joint
+                .bolt_id
+                .and_then(|id| self.library.bolt(id))
+                .is_some_and(|bolt| bolt.head_type == HeadType::Countersunk);
+            if !countersunk {
+                self.joint.head_washer_id = Some(place(&mut self.library, washer.clone()));
+            }
+            if self.joint.bolt_id.is_some() && self.joint.nut_id.is_some() {
+                self.joint.nut_washer_id = Some(place(&mut self.library, washer));
+            }
+        }
+
+        for id in spare {
+            self.library.remove(id);
+        }
+    }
+
+    /// Catalogue washer of a type for the thread designation
+    fn standard_washer(&self, kind: WasherType) -> Option<Washer> {
+        let thread = thread_catalogue::parse(&self.state.thread_designation).ok()?;
+        let diameter = match thread.unit {
+            Unit::Metric => thread.major_diameter,
+            Unit::Imperial => thread.major_diameter * 25.4,
+        };
+        let washer = washer_catalogue::standard(kind, diameter);
+        if washer.is_none() {
+            log::warn!(
+                "No {} for a {diameter} mm thread",
+                washer_catalogue::designation(kind, diameter)
+            );
+        }
+        washer
+    }
+
+    fn show_thread_summary(ui: &mut egui::Ui, designation: &str) {
+        match thread_catalogue::parse(designation) {
+            Ok(thread) => {
+                let unit = match thread.unit {
+                    Unit::Metric => "mm",
+                    Unit::Imperial => "in",
+                };
+                let label = ui.label(format!(
+                    "d2 = {:.3} {unit}, A_s = {:.4} {unit}²",
+                    thread.pitch_diameter(),
+                    thread.stress_area()
+                ));
+                if !thread_catalogue::is_standard(&thread) {
+                    let fine: Vec<String> = thread_catalogue::fine(thread.major_diameter)
+                        .iter()
+                        .map(|t| t.pitch.to_string())
+                        .collect();
+                    label.on_hover_text(match thread.unit {
+                        Unit::Metric if !fine.is_empty() => format!(
+                            "Not a catalogued pitch, fine pitches are {}",
+                            fine.join(", ")
+                        ),
+                        _ => "Not a catalogued pitch".to_string(),
+                    });
+                }
+            }
+            Err(error) => {
+                ui.label(egui::RichText::new(error.to_string()).color(egui::Color32::RED));
+            }
+        }
+    }
+
+    fn show_cycles(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            let mut finite = self.load.cycles.is_some();
+            ui.checkbox(&mut finite, "Finite life");
+            match (finite, &mut self.load.cycles) {
+                (true, Some(cycles)) => {
+                    ui.add(
+                        egui::DragValue::new(cycles)
+                            .range(fatigue::MIN_CYCLES..=fatigue::ENDURANCE_CYCLES)
+                            .speed(1000.0)
+                            .suffix(" cycles"),
+                    );
+                }
+                (true, None) => self.load.cycles = Some(fatigue::ENDURANCE_CYCLES / 10.0),
+                (false, _) => self.load.cycles = None,
+            }
+        });
+    }
+
+    fn show_transverse_load(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Shear:");
+            ui.add(
+                egui::DragValue::new(&mut self.load.transverse)
+                    .range(0.0..=f64::MAX)
+                    .suffix(" N"),
+            )
+            .on_hover_text("F_Qmax");
+            ui.add(
+                egui::DragValue::new(&mut self.load.torsion)
+                    .range(0.0..=f64::MAX)
+                    .suffix(" N·mm"),
+            )
+            .on_hover_text("M_Ymax");
+            ui.add(
+                egui::DragValue::new(&mut self.load.friction_radius)
+                    .range(0.0..=f64::MAX)
+                    .suffix(" mm"),
+            )
+            .on_hover_text("r_a");
+        });
+        ui.horizontal(|ui| {
+            ui.label("μT:");
+            ui.add(
+                egui::DragValue::new(&mut self.load.interface_friction)
+                    .range(0.0..=1.0)
+                    .speed(0.01),
+            )
+            .on_hover_text("Used for parts without their own friction coefficient");
+            ui.label("q:");
+            ui.add(
+                egui::DragValue::new(&mut self.load.friction_surfaces)
+                    .range(1.0..=f64::MAX)
+                    .speed(1.0),
+            )
+            .on_hover_text(
+                "Force transmitting interfaces q_F = q_M of a single part, \
+                 a stack of parts counts its own",
+            );
+            ui.checkbox(&mut self.load.slip_allowed, "Slip allowed");
+        });
+    }
+
+    fn show_eccentricity(&mut self, ui: &mut egui::Ui) {
+        let mut eccentric = self.joint.eccentricity.is_some();
+        ui.checkbox(&mut eccentric, "Eccentric");
+        match (eccentric, &mut self.joint.eccentricity) {
+            (true, Some(eccentricity)) => {
+                ui.horizontal(|ui| {
+                    for (value, hover) in [
+                        (&mut eccentricity.clamping, "s_sym, bolt axis offset"),
+                        (&mut eccentricity.load, "a, load line offset"),
+                    ] {
+                        ui.add(egui::DragValue::new(value).suffix(" mm"))
+                            .on_hover_text(hover);
+                    }
+                    for (value, hover) in [
+                        (&mut eccentricity.width, "b, substitute body width"),
+                        (&mut eccentricity.depth, "c_T, substitute body depth"),
+                    ] {
+                        ui.add(
+                            egui::DragValue::new(value)
+                                .range(1.0..=f64::MAX)
+                                .suffix(" mm"),
+                        )
+                        .on_hover_text(hover);
+                    }
+                });
+            }
+            (true, None) => self.joint.eccentricity = Some(Eccentricity::default()),
+            (false, _) => self.joint.eccentricity = None,
+        }
+    }
+
+    fn show_pattern(&mut self, ui: &mut egui::Ui) {
+        let mut enabled = self.pattern.is_some();
+        ui.checkbox(&mut enabled, "Analyse the critical bolt of a pattern");
+        match (enabled, &self.pattern) {
+            (true, None) => self.pattern = Some(BoltPattern::default()),
+            (false, Some(_)) => self.pattern = None,
+            _ => {}
+        }
+        let Some(pattern) = &mut self.pattern else {
+            return;
+        };
+
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_id_salt("pattern_layout")
+                .selected_text(match pattern.layout {
+                    Layout::Circular { .. } => "Circular",
+                    Layout::Rectangular { .. } => "Rectangular",
+                    Layout::Arbitrary(_) => "Arbitrary",
+                })
+                .show_ui(ui, |ui| {
+                    if ui.selectable_label(false, "Circular").clicked() {
+                        pattern.layout = BoltPattern::default().layout;
+                    }
+                    if ui.selectable_label(false, "Rectangular").clicked() {
+                        pattern.layout = Layout::Rectangular {
+                            rows: 2,
+                            columns: 3,
+                            pitch_x: 60.0,
+                            pitch_y: 60.0,
+                        };
+                    }
+                    if ui.selectable_label(false, "Arbitrary").clicked() {
+                        pattern.layout = Layout::Arbitrary(pattern.positions());
+                    }
+                });
+            match &mut pattern.layout {
+                Layout::Circular {
+                    count,
+                    radius,
+                    start_angle,
+                } => {
+                    ui.add(egui::DragValue::new(count).range(1..=64).suffix(" bolts"));
+                    ui.add(
+                        egui::DragValue::new(radius)
+                            .range(0.0..=f64::MAX)
+                            .suffix(" mm"),
+                    )
+                    .on_hover_text("Pitch circle radius");
+                    ui.add(egui::DragValue::new(start_angle).suffix("°"));
+                }
+                Layout::Rectangular {
+                    rows,
+                    columns,
+                    pitch_x,
+                    pitch_y,
+                } => {
+                    ui.add(egui::DragValue::new(rows).range(1..=32).suffix(" rows"));
+                    ui.add(egui::DragValue::new(columns).range(1..=32).suffix(" cols"));
+                    ui.add(
+                        egui::DragValue::new(pitch_x)
+                            .range(0.0..=f64::MAX)
+                            .suffix(" mm"),
+                    );
+                    ui.add(
+                        egui::DragValue::new(pitch_y)
+                            .range(0.0..=f64::MAX)
+                            .suffix(" mm"),
+                    );
+                }
+                Layout::Arbitrary(points) => {
+                    ui.label(format!("{} bolts", points.len()));
+                }
+            }
+        });
+        if let Layout::Arbitrary(points) = &mut pattern.layout {
+            let mut removed = None;
+            egui::Grid::new("pattern_points").show(ui, |ui| {
+                let single = points.len() == 1;
+                for (i, point) in points.iter_mut().enumerate() {
+                    ui.label(format!("{}", i + 1));
+                    ui.add(
+                        egui::DragValue::new(&mut point.x)
+                            .prefix("x ")
+                            .suffix(" mm"),
+                    );
+                    ui.add(
+                        egui::DragValue::new(&mut point.y)
+                            .prefix("y ")
+                            .suffix(" mm"),
+                    );
+                    if ui
+                        .add_enabled(!single, egui::Button::new("🗑"))
+                        .on_hover_text("Remove bolt")
+                        .clicked()
+                    {
+                        removed = Some(i);
+                    }
+                    ui.end_row();
+                }
+            });
+            if let Some(i) = removed {
+                points.remove(i);
+            }
+            if ui.button("Add bolt").clicked() {
+                // Next to the last bolt, so the new one does not hide under it
+                let next = points.last().map_or(Point::new(0.0, 0.0), |last| {
+                    Point::new(last.x + 20.0, last.y)
+                });
+                points.push(next);
+            }
+        }
+        ui.horizontal(|ui| {
+            let mut rigid = matches!(pattern.distribution, Distribution::Rigid { .. });
+            ui.checkbox(&mut rigid, "Rigid flange")
+                .on_hover_text("Tips about its edge instead of the bolt group centroid");
+            match (rigid, &mut pattern.distribution) {
+                (true, Distribution::Rigid { edge }) => {
+                    ui.add(
+                        egui::DragValue::new(edge)
+                            .range(0.0..=f64::MAX)
+                            .suffix(" mm"),
+                    )
+                    .on_hover_text("Centroid to the flange edge");
+                }
+                (true, Distribution::Elastic) => {
+                    pattern.distribution = Distribution::Rigid { edge: 0.0 };
+                }
+                (false, _) => pattern.distribution = Distribution::Elastic,
+            }
+        });
+        ui.horizontal(|ui| {
+            let loads = &mut self.pattern_loads;
+            for (value, label) in [
+                (&mut loads.fx, "Fx"),
+                (&mut loads.fy, "Fy"),
+                (&mut loads.fz, "Fz"),
+            ] {
+                ui.add(egui::DragValue::new(value).suffix(" N"))
+                    .on_hover_text(label);
+            }
+            for (value, label) in [
+                (&mut loads.mx, "Mx"),
+                (&mut loads.my, "My"),
+                (&mut loads.mz, "Mz"),
+            ] {
+                ui.add(egui::DragValue::new(value).suffix(" N·mm"))
+                    .on_hover_text(label);
+            }
+        });
+
+        let bolts = pattern.distribute(&self.pattern_loads);
+        let critical = pattern::critical(&bolts);
+        if let Some(i) = critical {
+            ui.label(format!(
+                "Critical bolt {}: F_A = {:.0} N, F_Q = {:.0} N",
+                i + 1,
+                bolts[i].axial,
+                bolts[i].shear
+            ));
+        }
+
+        let hole_diameter = self
+            .joint
+            .resolve(&self.library)
+            .ok()
+            .and_then(|joint| joint.clamped.iter().map(|c| c.id as f64).reduce(f64::max))
+            .unwrap_or(10.0);
+        let circles = pattern.circles(hole_diameter);
+        egui_plot::Plot::new("bolt_pattern")
+            .data_aspect(1.0)
+            .show_axes(false)
+            .show(ui, |plot_ui| {
+                for (i, circle) in circles.iter().enumerate() {
+                    let poly = circle.to_poly();
+                    if Some(i) == critical {
+                        plot_ui.polygon(poly.fill_color(egui::Color32::RED));
+                    } else {
+                        plot_ui.polygon(poly);
+                    }
+                }
+            });
+    }
+
+    /// Runs every combination of the sweep card's entries on the open joint, on a
+    /// background thread where there is one so a large grid does not stall the window
+    fn run_sweep(&mut self, ctx: &egui::Context) {
+        let sweep = match sweep_entries(&self.state.sweep) {
+            Ok(sweep) => sweep,
+            Err(error) => {
+                self.sweep = Some(Err(error.to_string()));
+                return;
+            }
+        };
+        let case = Case {
+            library: self.library.only(self.joint.references()),
+            joint: self.joint.clone(),
+            tightening: self.tightening.clone(),
+            load: self.load.clone(),
+            pattern: self.pattern.clone(),
+            pattern_loads: self.pattern_loads,
+            sweep,
+        };
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let (sender, receiver) = mpsc::channel();
+            let ctx = ctx.clone();
+            std::thread::spawn(move || {
+                let result = sweep::run(&case).map_err(|error| error.to_string());
+                // The window may have closed, leaving nobody to receive
+                let _ = sender.send(result);
+                ctx.request_repaint();
+            });
+            self.sweeping = Some(receiver);
+        }
+        // The web build cannot start threads, so the sweep runs within the frame
+        #[cfg(target_arch = "wasm32")]
+        {
+            let _ = ctx;
+            self.sweep = Some(sweep::run(&case).map_err(|error| error.to_string()));
+        }
+    }
+
+    fn show_sweep(&mut self, ui: &mut egui::Ui) {
+        let entries = &mut self.state.sweep;
+        egui::Grid::new("sweep_entries").show(ui, |ui| {
+            for (label, text, hint) in [
+                ("Thread", &mut entries.thread, "M10, M12"),
+                ("Class", &mut entries.property_class, "8.8, 10.9"),
+                ("μ", &mut entries.friction, "0.08..0.16 step 0.04"),
+                (
+                    "Clamp",
+                    &mut entries.clamp_thickness,
+                    "mm, blank keeps the joint's",
+                ),
+                ("T", &mut entries.temperature, "°C, blank keeps the joint's"),
+            ] {
+                ui.label(label);
+                ui.add(
+                    egui::TextEdit::singleline(text)
+                        .hint_text(hint)
+                        .desired_width(180.0),
+                );
+                ui.end_row();
+            }
+        });
+        if let Some(receiver) = &self.sweeping {
+            match receiver.try_recv() {
+                Ok(result) => {
+                    self.sweep = Some(result);
+                    self.sweeping = None;
+                }
+                Err(mpsc::TryRecvError::Empty) => {}
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    self.sweep = Some(Err("the sweep stopped without a result".to_string()));
+                    self.sweeping = None;
+                }
+            }
+        }
+        ui.horizontal(|ui| {
+            let running = self.sweeping.is_some();
+            if ui
+                .add_enabled(!running, egui::Button::new("Run sweep"))
+                .clicked()
+            {
+                self.run_sweep(ui.ctx());
+            }
+            if running {
+                ui.spinner();
+            }
+        });
+
+        let report = match &self.sweep {
+            None => return,
+            Some(Err(error)) => {
+                ui.colored_label(egui::Color32::RED, error);
+                return;
+            }
+            Some(Ok(report)) => report,
+        };
+        ui.label(format!(
+            "{} of {} combinations pass",
+            report.passed(),
+            report.points.len()
+        ));
+        let ids = report.check_ids();
+        egui::ScrollArea::both().show(ui, |ui| {
+            egui::Grid::new("sweep_results")
+                .striped(true)
+                .show(ui, |ui| {
+                    for heading in report.parameters() {
+                        ui.strong(heading);
+                    }
+                    ui.strong("F_Mzul");
+                    ui.strong("M_A");
+                    for id in &ids {
+                        ui.strong(*id);
+                    }
+                    ui.end_row();
+
+                    for point in &report.points {
+                        for (_, value) in point.variant.labels() {
+                            ui.label(value);
+                        }
+                        let Some(result) = &point.report else {
+                            ui.colored_label(egui::Color32::RED, "Not analysed")
+                                .on_hover_text(point.error.as_deref().unwrap_or_default());
+                            ui.end_row();
+                            continue;
+                        };
+                        let value = |symbol| result.value(symbol).unwrap_or(f64::NAN);
+                        ui.label(format!("{:.0} N", value("F_Mzul")));
+                        ui.label(format!("{:.1} N·m", value("M_A")));
+                        for id in &ids {
+                            let cell = match point.check(id) {
+                                Some(true) => ui.colored_label(egui::Color32::GREEN, "✔"),
+                                Some(false) => ui.colored_label(egui::Color32::RED, "✘"),
+                                None => ui.label("–"),
+                            };
+                            let details: Vec<String> = result
+                                .checks
+                                .iter()
+                                .filter(|c| c.id == *id)
+                                .map(|c| {
+                                    format!("{}: {:.2} / {:.2}", c.description, c.value, c.limit)
+                                })
+                                .collect();
+                            if !details.is_empty() {
+                                cell.on_hover_text(details.join("\n"));
+                            }
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+    }
+
+    fn show_tightening_method(&mut self, ui: &mut egui::Ui) {
+        let method = &mut self.tightening.method;
+        egui::ComboBox::from_id_salt("tightening_method")
+            .selected_text(match method {
+                TighteningMethod::Torque => "Torque",
+                TighteningMethod::Angle { .. } => "Angle",
+                TighteningMethod::Yield => "Yield",
+            })
+            .show_ui(ui, |ui| {
+                ui.selectable_value(method, TighteningMethod::Torque, "Torque");
+                if ui
+                    .selectable_label(matches!(method, TighteningMethod::Angle { .. }), "Angle")
+                    .clicked()
+                    && !matches!(method, TighteningMethod::Angle { .. })
+                {
+                    *method = TighteningMethod::Angle {
+                        snug_torque: 10.0,
+                        angle: 90.0,
+                    };
+                }
+                ui.selectable_value(method, TighteningMethod::Yield, "Yield");
+            });
+        if let TighteningMethod::Angle { snug_torque, angle } = method {
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::DragValue::new(snug_torque)
+                        .range(0.0..=f64::MAX)
+                        .suffix(" N·m"),
+                );
+                ui.add(egui::DragValue::new(angle).range(0.0..=720.0).suffix("°"));
+            });
+        }
+    }
+
+    fn show_thermal_settings(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.settings.thermal, "Thermal");
+            if self.settings.thermal {
+                ui.checkbox(&mut self.settings.sync_temp, "Sync")
+                    .on_hover_text("Bolt and clamped parts at the same temperature");
+            }
+        });
+        if self.settings.thermal {
+            ui.horizontal(|ui| {
+                if self.settings.sync_temp {
+                    ui.add(
+                        egui::DragValue::new(&mut self.settings.synced_temp)
+                            .range(-273.0..=1000.0)
+                            .suffix(" °C"),
+                    );
+                } else {
+                    let temperatures = self
+                        .load
+                        .temperature
+                        .get_or_insert_with(|| Temperatures::uniform(self.settings.synced_temp));
+                    ui.label("Bolt");
+                    ui.add(
+                        egui::DragValue::new(&mut temperatures.bolt)
+                            .range(-273.0..=1000.0)
+                            .suffix(" °C"),
+                    );
+                    ui.label("Parts");
+                    ui.add(
+                        egui::DragValue::new(&mut temperatures.clamped)
+                            .range(-273.0..=1000.0)
+                            .suffix(" °C"),
+                    );
+                }
+            });
+        }
+        self.load.temperature = Temperatures::from_state(&self.settings, self.load.temperature);
+    }
+
+    /// Copies a torque table for the joint's bolt class, ISO 8.8 when none is set
+    fn copy_torque_table(&self, ctx: &egui::Context) {
+        let class = self
+            .joint
+            .bolt_id
+            .and_then(|id| self.library.bolt(id))
+            .and_then(|bolt| bolt.material.property_class.clone())
+            .unwrap_or_else(|| "8.8".to_string());
+        let rows = torque::torque_table(
+            &thread_catalogue::series(ThreadForm::ISO),
+            &class,
+            self.tightening.mu_thread_min,
+            self.tightening.mu_head_min,
+            self.tightening.utilisation,
+        );
+        ctx.copy_text(torque::table_csv(&rows));
+    }
+
+    fn show_analysis(&self, ui: &mut egui::Ui) {
+        let joint = match self.joint.resolve(&self.library) {
+            Ok(joint) => joint,
+            Err(error) => {
+                ui.label(format!("Joint is incomplete: {error}"));
+                return;
+            }
+        };
+
+        let analysed = match &self.pattern {
+            Some(pattern) => pattern::analyse_pattern(
+                &joint,
+                &self.tightening,
+                &self.load,
+                pattern,
+                &self.pattern_loads,
+            )
+            .map(|pattern| (pattern.analysis, Some((pattern.bolts, pattern.critical)))),
+            None => analysis::analyse(&joint, &self.tightening, &self.load).map(|a| (a, None)),
+        };
+
+        match analysed {
+            Ok((result, bolts)) => {
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    egui::Grid::new("analysis_results").show(ui, |ui| {
+                        for (label, value) in [
+                            ("F_Mzul", format!("{:.0} N", result.preload_permissible)),
+                            ("F_Mmin", format!("{:.0} N", result.preload_min)),
+                            ("M_A", format!("{:.1} N·m", result.tightening_torque)),
+                            ("Φ", format!("{:.3}", result.load_factor)),
+                            ("F_PA", format!("{:.0} N", result.plate_relief)),
+                            ("f_Z", format!("{:.1} μm", result.embedding * 1000.0)),
+                            ("F_Z", format!("{:.0} N", result.embedding_loss)),
+                            ("ΔF_Vth", format!("{:.0} N", result.thermal_loss)),
+                            ("σ_redB", format!("{:.0} MPa", result.working_stress)),
+                            ("σ_a", format!("{:.1} MPa", result.alternating_stress)),
+                            ("σ_AS", format!("{:.1} MPa", result.endurance_limit)),
+                        ] {
+                            ui.label(label);
+                            ui.label(egui::RichText::new(value).strong());
+                            ui.end_row();
+                        }
+
+                        for check in &result.checks {
+                            let colour = if check.passed {
+                                egui::Color32::GREEN
+                            } else {
+                                egui::Color32::RED
+                            };
+                            ui.label(check.id).on_hover_text(check.description);
+                            ui.horizontal(|ui| {
+                                ui.label(
+                                    egui::RichText::new(format!(
+                                        "{:.2} / {:.2}",
+                                        check.value, check.limit
+                                    ))
+                                    .color(colour),
+                                );
+                                if let Some(warning) = check.warning {
+                                    ui.label(egui::RichText::new("⚠").color(egui::Color32::YELLOW))
+                                        .on_hover_text(warning);
+                                }
+                            });
+                            ui.end_row();
+                        }
+                    });
+
+                    ui.collapsing("Tightening", |ui| {
+                        let assembly = &result.assembly;
+                        egui::Grid::new("tightening").show(ui, |ui| {
+                            for (label, value) in [
+                                ("Pitch", assembly.torque.pitch),
+                                ("Thread friction", assembly.torque.thread_friction),
+                                ("Head friction", assembly.torque.head_friction),
+                                ("Prevailing", assembly.torque.prevailing),
+                            ] {
+                                ui.label(label);
+                                ui.label(format!("{value:.1} N·m"));
+                                ui.end_row();
+                            }
+                        });
+                        ui.label(format!(
+                            "F_M = {:.0} … {:.0} N, αA = {:.2}",
+                            assembly.preload_min,
+                            assembly.preload_max,
+                            assembly.scatter()
+                        ));
+                    });
+
+                    ui.collapsing("Surface pressure", |ui| {
+                        egui::Grid::new("surface_pressure").show(ui, |ui| {
+                            for face in &result.bearing {
+                                let colour = if face.assembly.max(face.service) <= face.limit {
+                                    egui::Color32::GREEN
+                                } else {
+                                    egui::Color32::RED
+                                };
+                                ui.label(if face.on_washer {
+                                    format!("Washer under {}", face.face.name())
+                                } else {
+                                    format!("Under {}", face.face.name())
+                                });
+                                ui.label(
+                                    egui::RichText::new(format!(
+                                        "{:.0} / {:.0} MPa",
+                                        face.assembly.max(face.service),
+                                        face.limit
+                                    ))
+                                    .color(colour),
+                                )
+                                .on_hover_text(format!(
+                                    "A_p = {:.1} mm² between {:.1} and {:.1} mm",
+                                    face.annulus.area(),
+                                    face.annulus.inner,
+                                    face.annulus.outer
+                                ));
+                                ui.end_row();
+                            }
+                        });
+                    });
+
+                    ui.collapsing("Thread engagement", |ui| {
+                        let stripping = &result.stripping;
+                        egui::Grid::new("thread_engagement").show(ui, |ui| {
+                            for (label, value) in [
+                                ("m_eff", format!("{:.1} mm", stripping.engaged)),
+                                ("m_eff,min", format!("{:.1} mm", stripping.required())),
+                                ("R_s", format!("{:.2}", stripping.strength_ratio)),
+                                ("F_mS", format!("{:.0} N", stripping.breaking_force)),
+                                (
+                                    "Stripping force",
+                                    format!("{:.0} N", stripping.stripping_force()),
+                                ),
+                                (
+                                    "C1 · C2 · C3",
+                                    format!(
+                                        "{:.3} · {:.3} · {:.3}",
+                                        stripping.dilation,
+                                        stripping.bolt_bending,
+                                        stripping.internal_bending
+                                    ),
+                                ),
+                            ] {
+                                ui.label(label);
+                                ui.label(value);
+                                ui.end_row();
+                            }
+                        });
+                        if let Some(anchored) = &result.anchor_stripping {
+                            ui.label(format!(
+                                "Anchored end: m_eff = {:.1} mm, m_eff,min = {:.1} mm",
+                                anchored.engaged,
+                                anchored.required()
+                            ));
+                        }
+                        if !stripping.bolt_fails_first() {
+                            ui.label(
+                                egui::RichText::new("Thread strips before the bolt breaks")
+                                    .color(egui::Color32::RED),
+                            );
+                        }
+                    });
+
+                    ui.collapsing("Slip", |ui| {
+                        let slip = &result.slip;
+                        ui.label(format!(
+                            "F_KQerf = {:.0} N, μ_Tmin = {:.2}, q_F = {}, S_G = {:.2}",
+                            slip.clamp_load_required,
+                            slip.friction,
+                            slip.surfaces,
+                            slip.safety(result.residual_clamp_load)
+                        ));
+                        if let Some(bearing) = &result.slip_bearing {
+                            egui::Grid::new("slip_bearing").show(ui, |ui| {
+                                for (label, value) in [
+                                    ("A_τ", format!("{:.1} mm²", bearing.shear_area)),
+                                    (
+                                        "τ_Q",
+                                        format!(
+                                            "{:.0} / {:.0} MPa",
+                                            bearing.shear_stress, bearing.shear_strength
+                                        ),
+                                    ),
+                                    (
+                                        "σ_L",
+                                        format!(
+                                            "{:.0} / {:.0} MPa",
+                                            bearing.hole_stress, bearing.hole_limit
+                                        ),
+                                    ),
+                                ] {
+                                    ui.label(label);
+                                    ui.label(value);
+                                    ui.end_row();
+                                }
+                            });
+                        }
+                    });
+
+                    if let Some(eccentric) = &result.eccentric {
+                        ui.collapsing("Eccentricity", |ui| {
+                            egui::Grid::new("eccentricity").show(ui, |ui| {
+                                for (label, value) in [
+                                    ("I_Bers", format!("{:.3e} mm⁴", eccentric.bending_inertia)),
+                                    (
+                                        "β_P",
+                                        format!("{:.3e} 1/(N·mm)", eccentric.bending_resilience),
+                                    ),
+                                    ("l_ers", format!("{:.1} mm", eccentric.bending_length)),
+                                    ("δ*_P", format!("{:.3e} mm/N", eccentric.plate_clamping)),
+                                    ("δ**_P", format!("{:.3e} mm/N", eccentric.plate_loading)),
+                                    ("F_Kab", format!("{:.0} N", eccentric.opening_limit)),
+                                    ("σ_SAb", format!("{:.1} MPa", eccentric.bending_stress)),
+                                ] {
+                                    ui.label(label);
+                                    ui.label(value);
+                                    ui.end_row();
+                                }
+                            });
+                        });
+                    }
+
+                    ui.collapsing("Fatigue", |ui| {
+                        let fatigue = &result.fatigue;
+                        let endurance = match fatigue.rolling {
+                            fatigue::Rolling::BeforeHeatTreatment => "σ_ASV",
+                            fatigue::Rolling::AfterHeatTreatment => "σ_ASG",
+                        };
+                        let cycles = fatigue
+                            .cycles
+                            .map_or("endurance".to_string(), |n| format!("{n:.0} cycles"));
+                        egui::Grid::new("fatigue").show(ui, |ui| {
+                            for (label, value) in [
+                                ("F_Sm", format!("{:.0} N", fatigue.mean_force)),
+                                (endurance, format!("{:.1} MPa", fatigue.endurance_limit)),
+                                ("σ_AS", format!("{:.1} MPa ({cycles})", fatigue.strength)),
+                                ("S_D", format!("{:.2}", fatigue.safety())),
+                            ] {
+                                ui.label(label);
+                                ui.label(value);
+                                ui.end_row();
+                            }
+                        });
+                    });
+
+                    if let Some(settling) = &result.settling {
+                        ui.collapsing("Embedding", |ui| {
+                            let interfaces = &settling.interfaces;
+                            egui::Grid::new("embedding").show(ui, |ui| {
+                                for (label, count, amount) in [
+                                    ("Thread", interfaces.threads, settling.thread),
+                                    ("Bearing faces", interfaces.bearings, settling.bearing),
+                                    ("Inner interfaces", interfaces.inner, settling.inner),
+                                ] {
+                                    ui.label(label);
+                                    ui.label(format!("{count} × {amount:.1} μm"));
+                                    ui.end_row();
+                                }
+                            });
+                        });
+                    }
+
+                    if let Some(thermal) = &result.thermal {
+                        ui.collapsing("Temperature", |ui| {
+                            let t = &thermal.temperatures;
+                            ui.label(format!(
+                                "Assembly {:.0} °C, bolt {:.0} °C, clamped parts {:.0} °C",
+                                t.assembly, t.bolt, t.clamped
+                            ));
+                            egui::Grid::new("thermal").show(ui, |ui| {
+                                for (label, value) in [
+                                    (
+                                        "Bolt growth",
+                                        format!("{:.1} μm", thermal.bolt_expansion * 1000.0),
+                                    ),
+                                    (
+                                        "Plate growth",
+                                        format!("{:.1} μm", thermal.plate_expansion * 1000.0),
+                                    ),
+                                    ("δS", format!("{:.3e} mm/N", thermal.bolt_resilience)),
+                                    ("δP", format!("{:.3e} mm/N", thermal.plate_resilience)),
+                                    ("F_V", format!("{:.0} N", thermal.preload)),
+                                    ("ΔF_Vth", format!("{:.0} N", thermal.loss)),
+                                ] {
+                                    ui.label(label);
+                                    ui.label(value);
+                                    ui.end_row();
+                                }
+                            });
+                        });
+                    }
+
+                    if let Some((bolts, critical)) = &bolts {
+                        ui.collapsing("Bolt loads", |ui| {
+                            egui::Grid::new("bolt_loads").show(ui, |ui| {
+                                for (i, bolt) in bolts.iter().enumerate() {
+                                    let text = format!(
+                                        "#{} ({:.1}, {:.1}) mm: F_A = {:.0} N, F_Q = {:.0} N",
+                                        i + 1,
+                                        bolt.position.x,
+                                        bolt.position.y,
+                                        bolt.axial,
+                                        bolt.shear
+                                    );
+                                    if i == *critical {
+                                        ui.label(egui::RichText::new(text).strong())
+                                            .on_hover_text("Critical bolt, analysed above");
+                                    } else {
+                                        ui.label(text);
+                                    }
+                                    ui.end_row();
+                                }
+                            });
+                        });
+                    }
+
+                    ui.collapsing("Bolt resilience", |ui| {
+                        egui::Grid::new("bolt_resilience").show(ui, |ui| {
+                            for (segment, (_, share)) in result
+                                .bolt_resilience
+                                .segments
+                                .iter()
+                                .zip(result.bolt_resilience.shares())
+                            {
+                                ui.label(segment.symbol).on_hover_text(segment.name);
+                                ui.label(format!("{:.3e} mm/N", segment.resilience()));
+                                ui.label(format!("{:.0} %", share * 100.0));
+                                ui.end_row();
+                            }
+                        });
+                    });
+
+                    ui.collapsing("Plate resilience", |ui| {
+                        let plates = &result.plate_resilience;
+                        ui.label(format!(
+                            "φ = {:.1}°, D_A,Gr = {:.1} mm",
+                            plates.cone.angle(),
+                            plates.cone.limit_diameter
+                        ));
+                        egui::Grid::new("plate_resilience").show(ui, |ui| {
+                            for (i, layer) in plates.layers.iter().enumerate() {
+                                ui.label(format!("Layer {}", i + 1))
+                                    .on_hover_text(format!("{:.1} mm below the head", layer.depth));
+                                ui.label(format!("{:.1} mm", layer.thickness));
+                                ui.label(format!("{:.3e} mm/N", layer.resilience));
+                                ui.end_row();
+                            }
+                        });
+                    });
+                });
+            }
+            Err(error) => {
+                ui.label(egui::RichText::new(error.to_string()).color(egui::Color32::RED));
+            }
+        }
+    }
+
     fn sized_card<F>(ui: &mut egui::Ui, title: &str, icon: &str, content: F)
     where
         F: FnOnce(&mut egui::Ui),
@@ -400,6 +1687,154 @@ impl Studio {
     //         });
     // }
 
+    /// Opens or saves the library and the current joint, JSON or TOML by extension
+    fn show_file_window(&mut self, ctx: &egui::Context, action: FileAction) {
+        let (title, button) = match action {
+            FileAction::Open => ("Open Library", "Open"),
+            FileAction::Save => ("Save Library", "Save"),
+            FileAction::Merge => ("Merge Library", "Compare"),
+        };
+        let mut open = true;
+        let mut done = false;
+        egui::Window::new(title)
+            .open(&mut open)
+            .resizable(false)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("File:");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.state.library_path)
+                            .desired_width(240.0),
+                    );
+                    if ui.button(button).clicked() {
+                        let path = Path::new(&self.state.library_path);
+                        let result = match action {
+                            FileAction::Open => library_file::load(path).map(|file| {
+                                for (joint, error) in file.library.validate() {
+                                    log::warn!(
+                                        "Stored joint \"{}\" is incomplete: {error}",
+                                        joint.name
+                                    );
+                                }
+                                self.library = file.library;
+                                self.joint = file.joint;
+                            }),
+                            FileAction::Save => {
+                                library_file::save(path, &self.library, &self.joint)
+                            }
+                            FileAction::Merge => library_file::load(path).map(|file| {
+                                self.merging = Some(Merging {
+                                    diff: library_merge::diff(&self.library, &file.library),
+                                    theirs: file.library,
+                                    current: 0,
+                                });
+                            }),
+                        };
+                        match result {
+                            Ok(()) => done = true,
+                            Err(error) => self.state.file_error = Some(error.to_string()),
+                        }
+                    }
+                });
+                if let Some(error) = &self.state.file_error {
+                    ui.label(egui::RichText::new(error).color(egui::Color32::RED));
+                }
+            });
+        if !open || done {
+            self.state.file_action = None;
+            self.state.file_error = None;
+        }
+    }
+
+    /// Summary of an incoming library, then each conflict in turn before merging
+    fn show_merge_window(&mut self, ctx: &egui::Context) {
+        let Some(merging) = &mut self.merging else {
+            return;
+        };
+        let mut open = true;
+        let mut merge = false;
+        egui::Window::new("Merge Library")
+            .open(&mut open)
+            .resizable(false)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                let diff = &mut merging.diff;
+                let new_joints = diff
+                    .joints
+                    .iter()
+                    .filter(|(_, change)| *change == JointChange::Added)
+                    .count();
+                ui.label(format!(
+                    "{} new, {} identical, {} duplicates, {} conflicts, {new_joints} new joints",
+                    diff.count(|c| matches!(c, Change::Added)),
+                    diff.count(|c| matches!(c, Change::Identical(_))),
+                    diff.count(|c| matches!(c, Change::Duplicate(_))),
+                    diff.conflicts().count(),
+                ));
+                ui.collapsing("Duplicates", |ui| {
+                    for difference in &diff.parts {
+                        if let Change::Duplicate(id) = difference.change {
+                            ui.label(format!(
+                                "{} \"{}\" is our {id}",
+                                difference.kind, difference.name
+                            ));
+                        }
+                    }
+                });
+                ui.collapsing("Incomplete joints", |ui| {
+                    for (name, change) in &diff.joints {
+                        if let JointChange::Dangling(id) = change {
+                            ui.label(format!("\"{name}\" refers to missing part {id}, left out"));
+                        }
+                    }
+                });
+                ui.separator();
+
+                let conflicts: Vec<usize> = (0..diff.parts.len())
+                    .filter(|&i| matches!(diff.parts[i].change, Change::Conflict { .. }))
+                    .collect();
+                match conflicts.get(merging.current) {
+                    Some(&i) => {
+                        let conflict = &mut diff.parts[i];
+                        ui.label(format!(
+                            "Conflict {} of {}: {} \"{}\"",
+                            merging.current + 1,
+                            conflicts.len(),
+                            conflict.kind,
+                            conflict.name
+                        ));
+                        if let Change::Conflict { fields, .. } = &conflict.change {
+                            ui.label(format!("Differs in {}", fields.join(", ")));
+                        }
+                        ui.horizontal(|ui| {
+                            for (resolution, label) in [
+                                (Resolution::KeepOurs, "Keep ours"),
+                                (Resolution::TakeTheirs, "Take theirs"),
+                                (Resolution::KeepBoth, "Keep both"),
+                            ] {
+                                if ui.button(label).clicked() {
+                                    conflict.resolution = resolution;
+                                    merging.current += 1;
+                                }
+                            }
+                        });
+                    }
+                    None => {
+                        if ui.button("Merge").clicked() {
+                            merge = true;
+                        }
+                    }
+                }
+            });
+        if merge {
+            library_merge::merge(&mut self.library, &merging.theirs, &merging.diff);
+        }
+        if !open || merge {
+            self.merging = None;
+        }
+    }
+
     fn show_settings_window(&mut self, ctx: &egui::Context) {
         egui::Window::new("Settings")
             .open(&mut self.state.show_settings)
@@ -413,6 +1848,17 @@ impl Studio {
     }
 }
 
+/// The sweep typed into the card
+fn sweep_entries(entries: &SweepEntries) -> Result<Sweep, SweepError> {
+    Ok(Sweep {
+        thread: sweep::list(&entries.thread),
+        property_class: sweep::list(&entries.property_class),
+        friction: Values::parse(&entries.friction)?,
+        clamp_thickness: Values::parse(&entries.clamp_thickness)?,
+        temperature: Values::parse(&entries.temperature)?,
+    })
+}
+
 impl eframe::App for Studio {
     /// Called by the frame work to save state before shutdown.
     fn save(&mut self, storage: &mut dyn eframe::Storage) {
@@ -448,3 +1894,46 @@ impl eframe::App for Studio {
         self.show_main_panel(ctx);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::elements::Stud;
+
+    #[test]
+    fn a_class_goes_to_the_joints_stud() {
+        let mut studio = Studio::default();
+        let thread = thread_catalogue::parse("M12").unwrap();
+        let stud = studio.library.insert(
+            String::new(),
+            Stud {
+                thread_a: thread.clone(),
+                thread_b: thread.clone(),
+                ..Default::default()
+            },
+        );
+        let nut = studio.library.insert(
+            String::new(),
+            Nut {
+                thread,
+                ..Default::default()
+            },
+        );
+        studio.joint.stud_id = Some(stud);
+        studio.joint.nut_id = Some(nut);
+
+        studio.apply_bolt_class("10.9");
+        assert_eq!(studio.joint.bolt_id, None);
+        let stud = studio.library.stud(stud).unwrap();
+        assert_eq!(stud.material.property_class.as_deref(), Some("10.9"));
+        assert!(
+            studio
+                .library
+                .nut(nut)
+                .unwrap()
+                .material
+                .property_class
+                .is_some()
+        );
+    }
+}
@@ -0,0 +1,5 @@
+#![warn(clippy::all, rust_2018_idioms)]
+
+fn main() -> std::process::ExitCode {
+    bolted_joints::cli::run(std::env::args().skip(1))
+}
@@ -0,0 +1,232 @@
+//! Headless entry point behind the `bjs` binary
+
+use std::path::Path;
+use std::process::ExitCode;
+
+use crate::modules::{
+    calc::{self, Report},
+    sweep,
+};
+
+const USAGE: &str = "\
+usage: bjs calc <input.toml|input.json> [--format table|json|csv]
+       bjs sweep <input.toml|input.json> [--format table|json|csv]
+
+calc analyses the joint an input file names in its library and prints the results.
+Exits with 1 when any check fails and 2 when the input cannot be analysed.
+
+sweep analyses every combination of the input's [sweep] table and prints the results
+with a pass/fail matrix of the checks. Exits with 1 when no combination passes.";
+
+/// Output format of the results
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Output {
+    Table,
+    Json,
+    Csv,
+}
+
+impl Output {
+    fn parse(text: &str) -> Option<Self> {
+        match text {
+            "table" => Some(Self::Table),
+            "json" => Some(Self::Json),
+            "csv" => Some(Self::Csv),
+            _ => None,
+        }
+    }
+}
+
+/// Runs the command line, `args` excluding the program name
+pub fn run(args: impl IntoIterator<Item = String>) -> ExitCode {
+    let (code, out) = execute(args);
+    print!("{out}");
+    code
+}
+
+/// Runs the command line and returns what it prints on standard output
+fn execute(args: impl IntoIterator<Item = String>) -> (ExitCode, String) {
+    let args: Vec<String> = args.into_iter().collect();
+    let mut input = None;
+    let mut output = Output::Table;
+    let mut rest = args.iter().skip(1);
+    let command = args.first().map(String::as_str);
+    if !matches!(command, Some("calc" | "sweep")) {
+        return (usage(), String::new());
+    }
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--format" | "-f" => match rest.next().and_then(|f| Output::parse(f)) {
+                Some(format) => output = format,
+                None => return (usage(), String::new()),
+            },
+            "--help" | "-h" => return (ExitCode::SUCCESS, format!("{USAGE}\n")),
+            path if input.is_none() => input = Some(path),
+            _ => return (usage(), String::new()),
+        }
+    }
+    let Some(input) = input else {
+        return (usage(), String::new());
+    };
+
+    let case = match calc::load(Path::new(input)) {
+        Ok(case) => case,
+        Err(error) => {
+            eprintln!("bjs: {input}: {error}");
+            return (ExitCode::from(2), String::new());
+        }
+    };
+    if command == Some("sweep") {
+        return run_sweep(input, &case, output);
+    }
+
+    let analysis = match case.analyse() {
+        Ok(analysis) => analysis,
+        Err(error) => {
+            eprintln!("bjs: {input}: {error}");
+            return (ExitCode::from(2), String::new());
+        }
+    };
+    let report = Report::new(&analysis);
+    let out = match output {
+        Output::Table => report.table(),
+        Output::Json => report.json() + "\n",
+        Output::Csv => report.csv(),
+    };
+    if report.passed {
+        (ExitCode::SUCCESS, out)
+    } else {
+        (ExitCode::FAILURE, out)
+    }
+}
+
+fn run_sweep(input: &str, case: &calc::Case, output: Output) -> (ExitCode, String) {
+    let report = match sweep::run(case) {
+        Ok(report) => report,
+        Err(error) => {
+            eprintln!("bjs: {input}: {error}");
+            return (ExitCode::from(2), String::new());
+        }
+    };
+    let out = match output {
+        Output::Table => format!("{}\n{}", report.table(), report.matrix()),
+        Output::Json => report.json() + "\n",
+        Output::Csv => report.csv(),
+    };
+    if report.passed() > 0 {
+        (ExitCode::SUCCESS, out)
+    } else {
+        (ExitCode::FAILURE, out)
+    }
+}
+
+fn usage() -> ExitCode {
+    eprintln!("{USAGE}");
+    ExitCode::from(2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::{
+        elements::Clamped,
+        fastener_catalogue,
+        joint::BoltedJoint,
+        library::Library,
+        library_file,
+        material::{Material, MaterialType},
+        property_class,
+    };
+
+    /// Writes an M12 8.8 through bolt library and one input file per load
+    fn fixture() -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("bjs-cli-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let mut library = Library::default();
+        let mut bolt = fastener_catalogue::bolt("ISO 4014 M12x60").unwrap();
+        let mut nut = fastener_catalogue::nut("ISO 4032 M12").unwrap();
+        property_class::assign("8.8", &bolt.thread, &mut bolt.material, Some(&mut nut));
+        let mut plate = Material::new("Steel", MaterialType::Metal);
+        plate.youngs_modulus = Some(205e9);
+        plate.tensile_strength = Some(700e6);
+        plate.limiting_pressure = Some(700e6);
+        let joint = BoltedJoint {
+            name: "Flange".to_string(),
+            bolt_id: Some(library.insert(String::new(), bolt)),
+            nut_id: Some(library.insert(String::new(), nut)),
+            clamped_ids: vec![library.insert(
+                String::new(),
+                Clamped {
+                    id: 13.5,
+                    thickness: 40.0,
+                    material: plate,
+                    ..Default::default()
+                },
+            )],
+            ..Default::default()
+        };
+        library.add_joint(joint.clone());
+        library_file::save(&dir.join("library.json"), &library, &joint).unwrap();
+
+        for (name, joint, axial) in [
+            ("pass", "Flange", 10_000.0),
+            ("fail", "Flange", 200_000.0),
+            ("missing", "Bracket", 10_000.0),
+        ] {
+            let input = format!(
+                "library = \"library.json\"\njoint = \"{joint}\"\n[load]\naxial_max = {axial}\n"
+            );
+            std::fs::write(dir.join(format!("{name}.toml")), input).unwrap();
+        }
+        dir
+    }
+
+    fn calc(dir: &std::path::Path, input: &str, format: &str) -> (ExitCode, String) {
+        let input = dir.join(input).display().to_string();
+        execute(["calc", &input, "--format", format].map(String::from))
+    }
+
+    #[test]
+    fn exit_codes_and_output_formats() {
+        let dir = fixture();
+
+        let (code, csv) = calc(&dir, "pass.toml", "csv");
+        assert_eq!(code, ExitCode::SUCCESS);
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some("kind,id,description,value,limit,unit,passed,warning")
+        );
+        assert!(lines.clone().any(|l| l.starts_with("result,F_Mzul,")));
+        assert!(
+            lines
+                .filter(|l| l.starts_with("check,"))
+                .all(|l| l.contains(",true,"))
+        );
+
+        let (code, json) = calc(&dir, "fail.toml", "json");
+        assert_eq!(code, ExitCode::FAILURE);
+        let report: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(report["passed"], false);
+        let checks = report["checks"].as_array().unwrap();
+        assert!(checks.iter().any(|c| c["passed"] == false));
+        assert!(
+            report["results"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .any(|r| r["symbol"] == "F_SA")
+        );
+
+        // Unknown joint, unreadable file and bad arguments are all input errors
+        assert_eq!(
+            calc(&dir, "missing.toml", "csv"),
+            (ExitCode::from(2), String::new())
+        );
+        assert_eq!(calc(&dir, "absent.toml", "csv").0, ExitCode::from(2));
+        assert_eq!(calc(&dir, "pass.toml", "xml").0, ExitCode::from(2));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
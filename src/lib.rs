@@ -1,5 +1,6 @@
 #![warn(clippy::all, rust_2018_idioms)]
 
 mod app;
+pub mod cli;
 mod modules;
 pub use app::Studio;
@@ -0,0 +1,1053 @@
+use std::f64::consts::PI;
+use std::fmt;
+
+use crate::modules::{
+    bearing::{BearingPressure, Face, WasherFace, bearing_pressures},
+    eccentricity::{Eccentric, eccentric},
+    elements::{Clamped, HeadType},
+    embedding::{LoadDirection, Settling, settling},
+    engagement::{self, Stripping, shear_ratio, stripping},
+    fatigue::{Fatigue, fatigue},
+    joint::{Counterpart, Fastener, ResolvedJoint},
+    material::{Material, Property, RangeError},
+    resilience::{BoltResilience, PlateResilience, bolt_resilience, plate_resilience},
+    slip::{Slip, SlipBearing, slip, slip_bearing},
+    thermal::{Temperatures, ThermalChange, thermal_change},
+    thread::{Thread, Unit},
+    torque::{self, AssemblyPreload, Friction, TighteningMethod},
+};
+
+const MM_PER_INCH: f64 = 25.4;
+const PA_PER_MPA: f64 = 1e6;
+const COUNTERSINK_ANGLE: f64 = 90.0; // λ, ISO 10642 and ISO 2009 heads
+
+/// Minimum safety factors used by the VDI 2230 checks
+const MIN_SAFETY_YIELD: f64 = 1.0; // R8
+const MIN_SAFETY_FATIGUE: f64 = 1.2; // R9
+const MIN_SAFETY_SLIP: f64 = 1.2; // R12
+const MIN_SAFETY_SHEAR: f64 = 1.1; // R12, joints allowed to slip
+
+/// Tightening parameters (VDI 2230 R1, R7, R13)
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone)]
+#[serde(default)]
+pub struct Tightening {
+    pub alpha_a: f64,       // tightening factor αA
+    pub mu_thread_min: f64, // μG min
+    pub mu_thread_max: f64, // μG max
+    pub mu_head_min: f64,   // μK min
+    pub mu_head_max: f64,   // μK max
+    pub utilisation: f64,   // ν, fraction of yield used at assembly
+    pub method: TighteningMethod,
+}
+
+impl Default for Tightening {
+    fn default() -> Self {
+        Self {
+            alpha_a: 1.6, // torque wrench, experimentally determined torque
+            mu_thread_min: 0.10,
+            mu_thread_max: 0.14,
+            mu_head_min: 0.10,
+            mu_head_max: 0.14,
+            utilisation: 0.9,
+            method: TighteningMethod::Torque,
+        }
+    }
+}
+
+impl Tightening {
+    pub fn friction(&self) -> Friction {
+        Friction {
+            thread_min: self.mu_thread_min,
+            thread_max: self.mu_thread_max,
+            head_min: self.mu_head_min,
+            head_max: self.mu_head_max,
+        }
+    }
+}
+
+/// Service loads and requirements acting on a single bolt (N, N·mm, MPa, mm²)
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone)]
+#[serde(default)]
+pub struct LoadCase {
+    pub axial_max: f64,          // F_Amax
+    pub axial_min: f64,          // F_Amin
+    pub cycles: Option<f64>,     // N load cycles, None for endurance
+    pub transverse: f64,         // F_Qmax
+    pub torsion: f64,            // M_Ymax, torque about the bolt axis
+    pub friction_radius: f64,    // r_a, radius the torque is transmitted at
+    pub interface_friction: f64, // μ_T of parts that do not give their own
+    pub friction_surfaces: f64,  // q_F = q_M when no interface lies within the stack
+    pub slip_allowed: bool,      // transverse load carried by the shank in bearing
+    pub sealing_pressure: f64,   // p_imax
+    pub sealing_area: f64,       // A_D
+    pub load_introduction: f64,  // n
+    pub embedding: Option<f64>,  // f_Z (mm), estimated from the interfaces when None
+    pub roughness: f64,          // Rz of the interfaces (μm)
+    pub load_direction: LoadDirection,
+    pub thermal_loss: f64, // ΔF_Vth, used when no temperatures are given
+    pub temperature: Option<Temperatures>,
+}
+
+impl Default for LoadCase {
+    fn default() -> Self {
+        Self {
+            axial_max: 0.0,
+            axial_min: 0.0,
+            cycles: None,
+            transverse: 0.0,
+            torsion: 0.0,
+            friction_radius: 0.0,
+            interface_friction: 0.15,
+            friction_surfaces: 1.0,
+            slip_allowed: false,
+            sealing_pressure: 0.0,
+            sealing_area: 0.0,
+            load_introduction: 0.5,
+            embedding: None,
+            roughness: 16.0,
+            load_direction: LoadDirection::Axial,
+            thermal_loss: 0.0,
+            temperature: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AnalysisError {
+    MissingProperty {
+        part: &'static str,
+        property: &'static str,
+    },
+    InvalidGeometry(&'static str),
+    OutOfRange {
+        part: &'static str,
+        error: RangeError,
+    },
+}
+
+impl fmt::Display for AnalysisError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingProperty { part, property } => {
+                write!(f, "{part} material is missing {property}")
+            }
+            Self::InvalidGeometry(reason) => write!(f, "invalid geometry: {reason}"),
+            Self::OutOfRange { part, error } => write!(f, "{part} material {error}"),
+        }
+    }
+}
+
+impl std::error::Error for AnalysisError {}
+
+/// A single pass/fail assessment step
+#[derive(Debug, Clone)]
+pub struct Check {
+    pub id: &'static str,
+    pub description: &'static str,
+    pub value: f64,
+    pub limit: f64,
+    pub passed: bool,
+    pub warning: Option<&'static str>, // the check rests on an estimate
+}
+
+impl Check {
+    fn at_most(id: &'static str, description: &'static str, value: f64, limit: f64) -> Self {
+        Self {
+            id,
+            description,
+            value,
+            limit,
+            passed: value <= limit,
+            warning: None,
+        }
+    }
+
+    fn at_least(id: &'static str, description: &'static str, value: f64, limit: f64) -> Self {
+        Self {
+            id,
+            description,
+            value,
+            limit,
+            passed: value >= limit,
+            warning: None,
+        }
+    }
+
+    fn warn_if(self, condition: bool, warning: &'static str) -> Self {
+        Self {
+            warning: condition.then_some(warning).or(self.warning),
+            ..self
+        }
+    }
+}
+
+/// Results of the VDI 2230 Part 1 calculation chain (N, mm, MPa, N·m)
+#[derive(Debug, Clone)]
+pub struct Analysis {
+    pub clamp_length: f64,                 // l_K
+    pub bolt_resilience: BoltResilience,   // δS
+    pub plate_resilience: PlateResilience, // δP
+    pub load_factor: f64,                  // Φ_n, or Φ*_en of an eccentric joint
+    pub eccentric: Option<Eccentric>,      // set when the joint is eccentric
+    pub clamp_load_required: f64,          // F_Kerf
+    pub slip: Slip,                        // F_KQerf, required whether or not slip is allowed
+    pub slip_bearing: Option<SlipBearing>, // shank shear and hole bearing when slip is allowed
+    pub embedding: f64,                    // f_Z (mm)
+    pub settling: Option<Settling>,        // VDI 2230 table 5 estimate, None when f_Z is given
+    pub embedding_loss: f64,               // F_Z
+    pub thermal_loss: f64,                 // ΔF_Vth
+    pub thermal: Option<ThermalChange>,    // set when operating temperatures are given
+    pub preload_min: f64,                  // F_Mmin
+    pub preload_max: f64,                  // F_Mmax
+    pub preload_permissible: f64,          // F_Mzul
+    pub additional_bolt_force: f64,        // F_SA
+    pub plate_relief: f64,                 // F_PA
+    pub bolt_force_max: f64,               // F_Smax
+    pub working_stress: f64,               // σ_redB
+    pub alternating_stress: f64,           // σ_a
+    pub endurance_limit: f64,              // σ_ASV or σ_ASG, reduced to N cycles
+    pub fatigue: Fatigue,
+    pub surface_pressure: f64,               // p_max
+    pub bearing: Vec<BearingPressure>,       // per bearing face
+    pub stripping: Stripping,                // thread stripping detail behind R11
+    pub anchor_stripping: Option<Stripping>, // anchored end of a stud in its tapped part
+    pub residual_clamp_load: f64,            // F_KRmin
+    pub tightening_torque: f64,              // M_A (N·m)
+    pub assembly: AssemblyPreload,           // preload range reached by the tightening method
+    pub checks: Vec<Check>,
+}
+
+impl Analysis {
+    pub fn passed(&self) -> bool {
+        self.checks.iter().all(|c| c.passed)
+    }
+}
+
+/// Fastener geometry reduced to the values the calculation chain needs (mm)
+pub(crate) struct Section {
+    pub d: f64,     // nominal diameter
+    pub p: f64,     // pitch
+    pub d2: f64,    // pitch diameter
+    pub d3: f64,    // minor diameter
+    pub d_1: f64,   // internal minor diameter D1
+    pub d_s: f64,   // stress area diameter
+    pub d_0: f64,   // smallest diameter carrying the preload, d_S or a waisted shank d_T
+    pub flank: f64, // flank angle β/2 (rad)
+}
+
+impl Section {
+    pub fn from_thread(thread: &Thread) -> Self {
+        let scale = match thread.unit {
+            Unit::Metric => 1.0,
+            Unit::Imperial => MM_PER_INCH,
+        };
+        Self {
+            d: thread.major_diameter * scale,
+            p: thread.pitch * scale,
+            d2: thread.pitch_diameter() * scale,
+            d3: thread.external_minor_diameter() * scale,
+            d_1: thread.internal_minor_diameter() * scale,
+            d_s: thread.stress_diameter() * scale,
+            d_0: thread.stress_diameter() * scale,
+            flank: thread.flank_angle().to_radians(),
+        }
+    }
+
+    pub fn nominal_area(&self) -> f64 {
+        PI / 4.0 * self.d * self.d
+    }
+
+    pub fn minor_area(&self) -> f64 {
+        PI / 4.0 * self.d3 * self.d3
+    }
+
+    pub fn stress_diameter(&self) -> f64 {
+        self.d_s
+    }
+
+    pub fn stress_area(&self) -> f64 {
+        PI / 4.0 * self.d_s * self.d_s
+    }
+
+    /// Takes a shank thinner than the stress diameter as the preload carrying section
+    pub fn waisted(mut self, shank_diameter: f64) -> Self {
+        if shank_diameter > 0.0 {
+            self.d_0 = self.d_0.min(shank_diameter);
+        }
+        self
+    }
+
+    /// Diameter d_0 the assembly and working stresses are taken on
+    pub fn min_diameter(&self) -> f64 {
+        self.d_0
+    }
+
+    /// Cross section A_0 the assembly and working stresses are taken on
+    pub fn min_area(&self) -> f64 {
+        PI / 4.0 * self.d_0 * self.d_0
+    }
+}
+
+/// Converts a material property from Pa to MPa, flagging it when missing
+fn property(
+    value: Option<f64>,
+    part: &'static str,
+    property: &'static str,
+) -> Result<f64, AnalysisError> {
+    value
+        .map(|v| v / PA_PER_MPA)
+        .ok_or(AnalysisError::MissingProperty { part, property })
+}
+
+/// Material property at a temperature in its stored units, flagging it when missing
+pub(crate) fn property_at(
+    material: &Material,
+    part: &'static str,
+    property: Property,
+    temperature: f64,
+) -> Result<f64, AnalysisError> {
+    material
+        .at(property, temperature)
+        .map_err(|error| AnalysisError::OutOfRange { part, error })?
+        .ok_or(AnalysisError::MissingProperty {
+            part,
+            property: property.name(),
+        })
+}
+
+pub(crate) fn youngs_modulus(
+    material: &Material,
+    part: &'static str,
+) -> Result<f64, AnalysisError> {
+    property(material.youngs_modulus, part, "Young's modulus")
+}
+
+fn yield_strength(material: &Material, part: &'static str) -> Result<f64, AnalysisError> {
+    property(material.yield_strength, part, "yield strength")
+}
+
+fn tensile_strength(material: &Material, part: &'static str) -> Result<f64, AnalysisError> {
+    property(material.tensile_strength, part, "tensile strength")
+}
+
+/// Runs the VDI 2230 Part 1 calculation steps R0 to R13 for one bolt
+pub fn analyse(
+    joint: &ResolvedJoint<'_>,
+    tightening: &Tightening,
+    load: &LoadCase,
+) -> Result<Analysis, AnalysisError> {
+    let (section, material, bolt_part) = match joint.fastener {
+        Fastener::Bolt(bolt) => (Section::from_thread(&bolt.thread), &bolt.material, "Bolt"),
+        // The nut is turned on thread_b, so that end carries the tightening torsion
+        Fastener::Stud(stud) => (
+            Section::from_thread(&stud.thread_b).waisted(stud.shank_diameter as f64),
+            &stud.material,
+            "Stud",
+        ),
+    };
+    let r_p = yield_strength(material, bolt_part)?;
+    let r_m = tensile_strength(material, bolt_part)?;
+
+    // Washers are clamped with the parts and count towards l_K
+    let stack = joint.stack();
+    let layers: Vec<&Clamped> = stack.iter().collect();
+    let clamp_length: f64 = layers.iter().map(|c| c.thickness as f64).sum();
+    if clamp_length <= 0.0 {
+        return Err(AnalysisError::InvalidGeometry(
+            "clamp length must be positive",
+        ));
+    }
+    let hole_diameter = joint
+        .clamped
+        .iter()
+        .map(|c| c.id as f64)
+        .fold(0.0, f64::max);
+    let bearing_diameter = match (&joint.fastener, &joint.counterpart) {
+        (Fastener::Bolt(bolt), _) => bolt.bearing_od as f64,
+        (Fastener::Stud(_), Counterpart::Nut(nut)) => nut.bearing_od as f64,
+        (Fastener::Stud(_), Counterpart::Threaded(_)) => {
+            return Err(AnalysisError::InvalidGeometry("a stud requires a nut"));
+        }
+    };
+
+    let mut checks = Vec::new();
+
+    // R0 - the substitute model holds only for d_W > d_h, plate_resilience rejects
+    // the joint otherwise
+
+    // R3 - resiliences and load factor
+    let bolt_resilience = bolt_resilience(joint, clamp_length)?;
+    let delta_s = bolt_resilience.total();
+    let plate_resilience = plate_resilience(&layers, bearing_diameter, joint.joint_type())?;
+    let delta_p = plate_resilience.total();
+    let eccentric = match joint.eccentricity {
+        Some(eccentricity) => Some(eccentric(
+            &eccentricity,
+            &bolt_resilience,
+            &plate_resilience,
+            youngs_modulus(material, bolt_part)?,
+            load.load_introduction,
+            hole_diameter,
+            section.stress_diameter(),
+            section.minor_area(),
+            section.stress_area(),
+            load.axial_max,
+        )),
+        None => None,
+    };
+    let phi = match &eccentric {
+        Some(eccentric) => eccentric.load_factor,
+        None => load.load_introduction * delta_p / (delta_s + delta_p),
+    };
+    let additional_bolt_force = phi * load.axial_max;
+    let plate_relief = (1.0 - phi) * load.axial_max;
+
+    // R2 - required clamp load
+    let slip = slip(
+        joint,
+        load.transverse,
+        load.torsion,
+        load.friction_radius,
+        load.interface_friction,
+        load.friction_surfaces,
+    );
+    // A joint allowed to slip carries the transverse load in bearing instead
+    let slip_clamp_load = if load.slip_allowed {
+        0.0
+    } else {
+        slip.clamp_load_required
+    };
+    let sealing_clamp_load = load.sealing_pressure * load.sealing_area;
+    let opening_clamp_load = eccentric.as_ref().map_or(0.0, |e| e.opening_limit);
+    let clamp_load_required = slip_clamp_load
+        .max(sealing_clamp_load)
+        .max(opening_clamp_load);
+
+    // R4 - preload changes
+    let settling = match load.embedding {
+        Some(_) => None,
+        None => Some(settling(joint, load.roughness, load.load_direction)),
+    };
+    let embedding = load
+        .embedding
+        .or_else(|| settling.as_ref().map(Settling::total))
+        .unwrap_or_default();
+    let embedding_loss = embedding / (delta_s + delta_p);
+    let thermal = match load.temperature {
+        Some(temperatures) => Some(thermal_change(
+            joint,
+            &bolt_resilience,
+            &plate_resilience,
+            temperatures,
+            clamp_load_required + plate_relief + embedding_loss,
+        )?),
+        None => None,
+    };
+    let thermal_loss = thermal.as_ref().map_or(load.thermal_loss, |t| t.loss);
+
+    // R5, R6 - minimum and maximum assembly preload
+    let preload_min = clamp_load_required + plate_relief + embedding_loss + thermal_loss;
+    let preload_max = tightening.alpha_a * preload_min;
+
+    // Preload range of the tightening method, turning the nut where there is one. The
+    // normal force on a countersunk head's cone raises its friction by 1/sin(λ/2).
+    let head_friction_diameter = match joint.fastener {
+        Fastener::Bolt(bolt) if bolt.head_type == HeadType::Countersunk => {
+            torque::friction_diameter(bolt.bearing_face(), hole_diameter)
+                / (COUNTERSINK_ANGLE / 2.0).to_radians().sin()
+        }
+        _ => torque::friction_diameter(bearing_diameter, hole_diameter),
+    };
+    let (friction_diameter, prevailing) = match joint.counterpart {
+        Counterpart::Nut(nut) if nut.bearing_od > nut.bearing_id && nut.bearing_id > 0.0 => (
+            torque::friction_diameter(nut.bearing_od as f64, nut.bearing_id as f64),
+            nut.prev_trq.unwrap_or(0.0) as f64,
+        ),
+        Counterpart::Nut(nut) => (head_friction_diameter, nut.prev_trq.unwrap_or(0.0) as f64),
+        Counterpart::Threaded(_) => (head_friction_diameter, 0.0),
+    };
+    let assembly = torque::assembly_preload(
+        tightening.method,
+        &section,
+        &tightening.friction(),
+        r_p,
+        tightening.utilisation,
+        tightening.alpha_a,
+        friction_diameter,
+        prevailing,
+        delta_s + delta_p,
+    );
+
+    // R7 - assembly stress
+    let a_0 = section.min_area();
+    let d_0 = section.min_diameter();
+    let preload_permissible = torque::yield_preload(
+        &section,
+        r_p,
+        tightening.mu_thread_min,
+        tightening.utilisation,
+    );
+    match tightening.method {
+        TighteningMethod::Torque => checks.push(Check::at_most(
+            "R7",
+            "Maximum assembly preload F_Mmax ≤ F_Mzul",
+            preload_max,
+            preload_permissible,
+        )),
+        _ => checks.push(Check::at_least(
+            "R7",
+            "Assembly preload reached F_Mmin,ist ≥ F_Mmin",
+            assembly.preload_min,
+            preload_min,
+        )),
+    }
+
+    // R8 - working stress, against the yield strength at the bolt's operating temperature
+    let r_p_service = match load.temperature {
+        Some(temperatures) => {
+            property_at(
+                material,
+                bolt_part,
+                Property::YieldStrength,
+                temperatures.bolt,
+            )? / PA_PER_MPA
+        }
+        None => r_p,
+    };
+    let bolt_force_max = assembly.preload_max + additional_bolt_force - thermal_loss;
+    let sigma_z = bolt_force_max / a_0;
+    let thread_term =
+        section.p / (PI * section.d2) + tightening.mu_thread_min / section.flank.cos();
+    let thread_torque = assembly.preload_max * section.d2 / 2.0 * thread_term;
+    let tau = thread_torque / (PI * d_0.powi(3) / 16.0);
+    let working_stress = (sigma_z.powi(2) + 3.0 * (0.5 * tau).powi(2)).sqrt();
+    match tightening.method {
+        TighteningMethod::Torque => checks.push(Check::at_least(
+            "R8",
+            "Safety against yield S_F",
+            r_p_service / working_stress,
+            MIN_SAFETY_YIELD,
+        )),
+        // Tightened into yield, only a small additional force is tolerated
+        _ => checks.push(Check::at_most(
+            "R8",
+            "Additional bolt force F_SA ≤ 0.1·R_p0.2·A_S",
+            additional_bolt_force,
+            0.1 * r_p * a_0,
+        )),
+    }
+
+    // R9 - alternating stress against the endurance or finite life strength
+    let rolling = match joint.fastener {
+        Fastener::Bolt(bolt) => bolt.rolling,
+        Fastener::Stud(stud) => stud.rolling,
+    };
+    let fatigue = fatigue(
+        rolling,
+        load.cycles,
+        section.d,
+        // The thread fails in fatigue, even below a waisted shank
+        section.stress_area(),
+        r_p,
+        phi,
+        eccentric.as_ref().map_or(1.0, |e| e.bending_factor),
+        assembly.preload_max,
+        load.axial_max,
+        load.axial_min,
+    );
+    let alternating_stress = fatigue.amplitude;
+    let endurance_limit = fatigue.strength;
+    if alternating_stress > 0.0 {
+        checks.push(Check::at_least(
+            "R9",
+            "Safety against fatigue S_D",
+            fatigue.safety(),
+            MIN_SAFETY_FATIGUE,
+        ));
+    }
+
+    // R10 - surface pressure under each bearing face at assembly and in service
+    let washers = joint.washers.map(|washer| {
+        washer.map(|w| WasherFace {
+            id: w.id as f64,
+            od: w.od as f64,
+            material: &w.material,
+        })
+    });
+    // Yield and angle controlled tightening go past F_Mzul, so the faces carry F_Mmax
+    let assembly_load = match tightening.method {
+        TighteningMethod::Yield | TighteningMethod::Angle { .. } => assembly.preload_max,
+        TighteningMethod::Torque => preload_permissible,
+    };
+    let bearing = bearing_pressures(joint, washers, assembly_load, bolt_force_max)?;
+    for face in &bearing {
+        let (assembly, service) = match (face.face, face.on_washer) {
+            (Face::Head, true) => (
+                "Washer under head p_M ≤ p_G at assembly",
+                "Washer under head p_B ≤ p_G in service",
+            ),
+            (Face::Head, false) => (
+                "Surface pressure under head p_M ≤ p_G at assembly",
+                "Surface pressure under head p_B ≤ p_G in service",
+            ),
+            (Face::Nut, true) => (
+                "Washer under nut p_M ≤ p_G at assembly",
+                "Washer under nut p_B ≤ p_G in service",
+            ),
+            (Face::Nut, false) => (
+                "Surface pressure under nut p_M ≤ p_G at assembly",
+                "Surface pressure under nut p_B ≤ p_G in service",
+            ),
+        };
+        let estimated = "p_G estimated as R_m, the material gives none";
+        checks.push(
+            Check::at_most("R10", assembly, face.assembly, face.limit)
+                .warn_if(face.estimated, estimated),
+        );
+        checks.push(
+            Check::at_most("R10", service, face.service, face.limit)
+                .warn_if(face.estimated, estimated),
+        );
+    }
+    let surface_pressure = bearing
+        .iter()
+        .map(|face| face.assembly.max(face.service))
+        .fold(0.0, f64::max);
+
+    // R11 - length of engagement, the bolt must break before either thread strips
+    let (engaged, internal_material, internal_part, nut_width) = match joint.counterpart {
+        // Without a width across flats the bearing diameter stands in, which is conservative
+        Counterpart::Nut(nut) => (
+            nut.thickness as f64,
+            &nut.material,
+            "Nut",
+            Some(nut.width_across_flats.unwrap_or(nut.bearing_od) as f64),
+        ),
+        Counterpart::Threaded(threaded) => (
+            threaded.thread_length as f64,
+            &threaded.material,
+            "Tapped part",
+            None,
+        ),
+    };
+    let stripping = stripping(
+        &section,
+        r_m,
+        shear_ratio(material),
+        tensile_strength(internal_material, internal_part)?,
+        shear_ratio(internal_material),
+        nut_width,
+        engaged,
+    );
+    checks.push(Check::at_least(
+        "R11",
+        "Length of engagement m_eff ≥ m_eff,min",
+        engaged,
+        stripping.required(),
+    ));
+    let anchor_stripping = match (&joint.fastener, joint.anchor) {
+        (Fastener::Stud(stud), Some(threaded)) => {
+            let anchor = Section::from_thread(&stud.thread_a).waisted(stud.shank_diameter as f64);
+            let engaged = stud.thread_length_a.min(threaded.thread_length) as f64;
+            let anchored = engagement::stripping(
+                &anchor,
+                r_m,
+                shear_ratio(material),
+                tensile_strength(&threaded.material, "Tapped part")?,
+                shear_ratio(&threaded.material),
+                None,
+                engaged,
+            );
+            checks.push(Check::at_least(
+                "R11",
+                "Anchored end engagement m_eff ≥ m_eff,min",
+                engaged,
+                anchored.required(),
+            ));
+            Some(anchored)
+        }
+        _ => None,
+    };
+
+    // R12 - residual clamp load and slipping
+    let residual_clamp_load = assembly.preload_min - plate_relief - embedding_loss - thermal_loss;
+    checks.push(Check::at_least(
+        "R12",
+        "Residual clamp load F_KRmin ≥ F_Kerf",
+        residual_clamp_load,
+        clamp_load_required,
+    ));
+    if slip_clamp_load > 0.0 {
+        checks.push(Check::at_least(
+            "R12",
+            "Safety against slipping S_G",
+            slip.safety(residual_clamp_load),
+            MIN_SAFETY_SLIP,
+        ));
+    }
+    let slip_bearing = if load.slip_allowed && slip.clamp_load_required > 0.0 {
+        // The torque reaches the bolt as a transverse force at the friction radius
+        let torsion = if load.friction_radius > 0.0 {
+            load.torsion / load.friction_radius
+        } else {
+            0.0
+        };
+        let bearing = slip_bearing(joint, &section, load.transverse + torsion)?;
+        checks.push(Check::at_least(
+            "R12",
+            "Safety against shearing S_A",
+            bearing.shear_safety(),
+            MIN_SAFETY_SHEAR,
+        ));
+        checks.push(Check::at_most(
+            "R12",
+            "Hole bearing stress σ_L ≤ R_m",
+            bearing.hole_stress,
+            bearing.hole_limit,
+        ));
+        Some(bearing)
+    } else {
+        None
+    };
+
+    // R13 - tightening torque
+    let tightening_torque = assembly.torque.total();
+
+    Ok(Analysis {
+        clamp_length,
+        bolt_resilience,
+        plate_resilience,
+        load_factor: phi,
+        eccentric,
+        clamp_load_required,
+        slip,
+        slip_bearing,
+        embedding,
+        settling,
+        embedding_loss,
+        thermal_loss,
+        thermal,
+        preload_min,
+        preload_max,
+        preload_permissible,
+        additional_bolt_force,
+        plate_relief,
+        bolt_force_max,
+        working_stress,
+        alternating_stress,
+        endurance_limit,
+        fatigue,
+        surface_pressure,
+        bearing,
+        stripping,
+        anchor_stripping,
+        residual_clamp_load,
+        tightening_torque,
+        assembly,
+        checks,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::{
+        elements::{Bolt, Nut, Stud, Threaded},
+        joint::JointType,
+        material::MaterialType,
+        thread::ThreadHand,
+    };
+
+    fn steel(yield_strength: f64, tensile_strength: f64) -> Material {
+        let mut material = Material::new("Steel", MaterialType::Metal);
+        material.youngs_modulus = Some(205e9);
+        material.yield_strength = Some(yield_strength);
+        material.tensile_strength = Some(tensile_strength);
+        material
+    }
+
+    fn m12_joint() -> (Bolt, Nut, Clamped) {
+        let thread = Thread::new_metric(12.0, 1.75, None, ThreadHand::Right, None);
+        let bolt = Bolt {
+            thread: thread.clone(),
+            length: 60.0,
+            thread_length: 30.0,
+            head_thickness: 7.5,
+            bearing_od: 17.0,
+            material: steel(640e6, 800e6),
+            ..Default::default()
+        };
+        let nut = Nut {
+            thread,
+            bearing_id: 13.0,
+            bearing_od: 17.0,
+            thickness: 10.8,
+            material: steel(640e6, 800e6),
+            ..Default::default()
+        };
+        let clamped = Clamped {
+            id: 13.5,
+            od: None,
+            thickness: 40.0,
+            chamfer_top: None,
+            chamfer_bottom: None,
+            friction: None,
+            material: steel(490e6, 700e6),
+        };
+        (bolt, nut, clamped)
+    }
+
+    #[test]
+    fn m12_8_8_through_bolt() {
+        let (bolt, nut, clamped) = m12_joint();
+        let joint = ResolvedJoint {
+            fastener: Fastener::Bolt(&bolt),
+            counterpart: Counterpart::Nut(&nut),
+            clamped: vec![&clamped],
+            anchor: None,
+            washers: [None, None],
+            eccentricity: None,
+        };
+        let load = LoadCase {
+            axial_max: 10_000.0,
+            ..Default::default()
+        };
+        let result = analyse(&joint, &Tightening::default(), &load).unwrap();
+
+        // A_S = 84.3 mm², ν·R_p0.2 = 576 MPa reduced by the thread torsion
+        assert!((result.preload_permissible - 44_190.0).abs() < 10.0);
+        // Nut turned, D_Km from the nut bearing face
+        assert!((result.tightening_torque - 73.2).abs() < 0.1);
+        assert!(result.load_factor > 0.0 && result.load_factor < 0.5);
+        assert!(result.plate_resilience.total() < result.bolt_resilience.total());
+        assert!(result.passed());
+    }
+
+    #[test]
+    fn yield_tightening_bears_the_maximum_preload() {
+        let (bolt, nut, clamped) = m12_joint();
+        let joint = |clamped| ResolvedJoint {
+            fastener: Fastener::Bolt(&bolt),
+            counterpart: Counterpart::Nut(&nut),
+            clamped: vec![clamped],
+            anchor: None,
+            washers: [None, None],
+            eccentricity: None,
+        };
+        let tightening = Tightening {
+            method: TighteningMethod::Yield,
+            ..Default::default()
+        };
+        let result = analyse(&joint(&clamped), &tightening, &LoadCase::default()).unwrap();
+        let head = &result.bearing[0];
+        assert!(result.assembly.preload_max > result.preload_permissible);
+        let load = head.assembly * head.annulus.area();
+        assert!((load - result.assembly.preload_max).abs() < 1e-6);
+
+        // Without p_G the plate's R_m stands in and the R10 checks say so
+        let r10 = |result: &Analysis| {
+            result
+                .checks
+                .iter()
+                .filter(|c| c.id == "R10")
+                .map(|c| c.warning)
+                .collect::<Vec<_>>()
+        };
+        assert!(r10(&result).iter().all(Option::is_some));
+        let mut rated = clamped.clone();
+        rated.material.limiting_pressure = Some(700e6);
+        let result = analyse(&joint(&rated), &tightening, &LoadCase::default()).unwrap();
+        assert!(r10(&result).iter().all(Option::is_none));
+    }
+
+    #[test]
+    fn slip_allowed_checks_bearing_instead() {
+        let (bolt, nut, clamped) = m12_joint();
+        let (top, bottom) = (
+            Clamped {
+                thickness: 20.0,
+                ..clamped.clone()
+            },
+            Clamped {
+                thickness: 20.0,
+                ..clamped
+            },
+        );
+        let joint = ResolvedJoint {
+            fastener: Fastener::Bolt(&bolt),
+            counterpart: Counterpart::Nut(&nut),
+            clamped: vec![&top, &bottom],
+            anchor: None,
+            washers: [None, None],
+            eccentricity: None,
+        };
+        let mut load = LoadCase {
+            transverse: 6_000.0,
+            ..Default::default()
+        };
+        let held = analyse(&joint, &Tightening::default(), &load).unwrap();
+        assert!((held.clamp_load_required - 40_000.0).abs() < 1e-6);
+        assert!(held.slip_bearing.is_none());
+
+        load.slip_allowed = true;
+        let slipping = analyse(&joint, &Tightening::default(), &load).unwrap();
+        assert_eq!(slipping.clamp_load_required, 0.0);
+        let bearing = slipping.slip_bearing.unwrap();
+        assert!((bearing.shear_stress - 6_000.0 / (PI / 4.0 * 144.0)).abs() < 1e-9);
+        assert!(
+            slipping
+                .checks
+                .iter()
+                .any(|c| c.description.contains("S_A"))
+        );
+    }
+
+    #[test]
+    fn waisted_stud_into_tapped_part() {
+        let (bolt, nut, clamped) = m12_joint();
+        let stud = |shank_diameter| Stud {
+            thread_a: bolt.thread.clone(),
+            thread_length_a: 18.0,
+            thread_b: bolt.thread.clone(),
+            thread_length_b: 20.0,
+            shank_diameter,
+            shank_length: 30.0,
+            material: steel(640e6, 800e6),
+            ..Default::default()
+        };
+        let base = Threaded {
+            thread: bolt.thread.clone(),
+            thread_length: 24.0,
+            material: steel(490e6, 700e6),
+            ..Default::default()
+        };
+        let (full, waisted) = (stud(12.0), stud(9.0));
+        let analyse_stud = |stud| {
+            let joint = ResolvedJoint {
+                fastener: Fastener::Stud(stud),
+                counterpart: Counterpart::Nut(&nut),
+                clamped: vec![&clamped],
+                anchor: Some(&base),
+                washers: [None, None],
+                eccentricity: None,
+            };
+            analyse(&joint, &Tightening::default(), &LoadCase::default()).unwrap()
+        };
+        let (full, waisted) = (analyse_stud(&full), analyse_stud(&waisted));
+
+        // A_T = 63.6 mm² carries the preload instead of A_S = 84.3 mm²
+        assert!(waisted.preload_permissible < 0.8 * full.preload_permissible);
+        assert!(waisted.bolt_resilience.total() > full.bolt_resilience.total());
+        let anchored = waisted.anchor_stripping.unwrap();
+        assert_eq!(anchored.engaged, 18.0);
+        assert!(
+            waisted
+                .checks
+                .iter()
+                .any(|c| c.description.starts_with("Anchored end"))
+        );
+    }
+
+    #[test]
+    fn anchored_stud_clamps_like_a_tapped_joint() {
+        let (bolt, nut, clamped) = m12_joint();
+        let base = Threaded {
+            thread: bolt.thread.clone(),
+            thread_length: 24.0,
+            material: steel(490e6, 700e6),
+            ..Default::default()
+        };
+        let stud = Stud {
+            thread_a: bolt.thread.clone(),
+            thread_length_a: 18.0,
+            thread_b: bolt.thread.clone(),
+            thread_length_b: 20.0,
+            shank_diameter: 12.0,
+            shank_length: 30.0,
+            material: steel(640e6, 800e6),
+            ..Default::default()
+        };
+        let resolved = |fastener, counterpart, anchor| ResolvedJoint {
+            fastener,
+            counterpart,
+            clamped: vec![&clamped],
+            anchor,
+            washers: [None, None],
+            eccentricity: None,
+        };
+        let analyse_joint = |joint: &ResolvedJoint<'_>| {
+            analyse(joint, &Tightening::default(), &LoadCase::default()).unwrap()
+        };
+        let anchored = resolved(Fastener::Stud(&stud), Counterpart::Nut(&nut), Some(&base));
+        let tapped = resolved(Fastener::Bolt(&bolt), Counterpart::Threaded(&base), None);
+        let through = resolved(Fastener::Bolt(&bolt), Counterpart::Nut(&nut), None);
+        assert_eq!(anchored.joint_type(), JointType::TappedThread);
+
+        // Same stack and bearing diameter, so the one-sided cone gives the same δP
+        let (anchored, tapped, through) = (
+            analyse_joint(&anchored).plate_resilience.total(),
+            analyse_joint(&tapped).plate_resilience.total(),
+            analyse_joint(&through).plate_resilience.total(),
+        );
+        assert!((anchored - tapped).abs() < 1e-12 * tapped);
+        assert!((anchored - through).abs() > 1e-3 * through);
+    }
+
+    #[test]
+    fn countersunk_head_in_sheet() {
+        let (hex, _, clamped) = m12_joint();
+        let base = Threaded {
+            thread: hex.thread.clone(),
+            thread_length: 24.0,
+            material: steel(490e6, 700e6),
+            ..Default::default()
+        };
+        let countersunk = Bolt {
+            head_type: HeadType::Countersunk,
+            head_thickness: 7.44,
+            head_diameter: 26.88,
+            bearing_od: 19.44,
+            ..hex.clone()
+        };
+        let analyse_bolt = |bolt| {
+            let joint = ResolvedJoint {
+                fastener: Fastener::Bolt(bolt),
+                counterpart: Counterpart::Threaded(&base),
+                clamped: vec![&clamped],
+                anchor: None,
+                washers: [None, None],
+                eccentricity: None,
+            };
+            analyse(&joint, &Tightening::default(), &LoadCase::default()).unwrap()
+        };
+        let (hex, countersunk) = (analyse_bolt(&hex), analyse_bolt(&countersunk));
+
+        // Clamped from half way down the cone, bearing on the projected cone, with the
+        // head turned on the cone
+        assert!((countersunk.clamp_length - (40.0 - 3.72)).abs() < 1e-4);
+        assert_eq!(countersunk.bearing[0].annulus.outer, 26.88_f32 as f64);
+        assert!(countersunk.tightening_torque > hex.tightening_torque);
+    }
+
+    #[test]
+    fn missing_material_is_reported() {
+        let (mut bolt, nut, clamped) = m12_joint();
+        bolt.material.yield_strength = None;
+        let joint = ResolvedJoint {
+            fastener: Fastener::Bolt(&bolt),
+            counterpart: Counterpart::Nut(&nut),
+            clamped: vec![&clamped],
+            anchor: None,
+            washers: [None, None],
+            eccentricity: None,
+        };
+        let error = analyse(&joint, &Tightening::default(), &LoadCase::default()).unwrap_err();
+        assert_eq!(
+            error,
+            AnalysisError::MissingProperty {
+                part: "Bolt",
+                property: "yield strength"
+            }
+        );
+    }
+}
@@ -0,0 +1,301 @@
+use std::f64::consts::PI;
+
+use crate::modules::{
+    analysis::AnalysisError,
+    elements::{Clamped, HeadType},
+    joint::{Counterpart, Fastener, ResolvedJoint},
+    material::{Material, Property},
+};
+
+const PA_PER_MPA: f64 = 1e6;
+
+/// Which loaded face of the clamped stack
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Face {
+    Head, // under the bolt head, or the nut of a stud
+    Nut,  // under the nut of a through bolt
+}
+
+impl Face {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Face::Head => "head",
+            Face::Nut => "nut",
+        }
+    }
+}
+
+/// Washer between a bearing face and the clamped part (mm)
+#[derive(Debug, Clone)]
+pub struct WasherFace<'a> {
+    pub id: f64,
+    pub od: f64,
+    pub material: &'a Material,
+}
+
+/// Annulus carrying the preload on one face (mm)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Annulus {
+    pub outer: f64, // d_W, limited by the part or washer under it
+    pub inner: f64, // d_a, largest of the hole, chamfer and bearing bore
+}
+
+impl Annulus {
+    /// Bearing area A_p, zero when the bore swallows the face
+    pub fn area(&self) -> f64 {
+        (PI / 4.0 * (self.outer.powi(2) - self.inner.powi(2))).max(0.0)
+    }
+}
+
+/// Surface pressure at one bearing face (MPa)
+#[derive(Debug, Clone)]
+pub struct BearingPressure {
+    pub face: Face,
+    pub on_washer: bool, // the washer itself rather than the clamped part under it
+    pub annulus: Annulus,
+    pub assembly: f64,   // p_M = F_Mzul / A_p
+    pub service: f64,    // p_B = F_Smax / A_p
+    pub limit: f64,      // p_G
+    pub estimated: bool, // p_G taken as R_m, the material gives none
+}
+
+/// Limiting surface pressure p_G, estimated as R_m when the material does not give one (MPa)
+///
+/// The estimate is flagged on the face's `estimated` and warned about on its checks.
+pub fn limiting_pressure(material: &Material, part: &'static str) -> Result<f64, AnalysisError> {
+    material
+        .scalar(Property::LimitingPressure)
+        .or(material.tensile_strength)
+        .map(|p| p / PA_PER_MPA)
+        .ok_or(AnalysisError::MissingProperty {
+            part,
+            property: "limiting surface pressure",
+        })
+}
+
+/// Bearing annulus of a face; with a washer the part sees the washer's outer diameter
+///
+/// `chamfer` is the hole chamfer diameter on the loaded face of the part.
+pub fn annulus(
+    bearing_od: f64,
+    bearing_id: f64,
+    hole: f64,
+    chamfer: Option<f64>,
+    washer: Option<&WasherFace<'_>>,
+) -> Annulus {
+    let bore = hole.max(chamfer.unwrap_or(0.0));
+    match washer {
+        Some(washer) => Annulus {
+            outer: washer.od,
+            inner: bore.max(washer.id),
+        },
+        None => Annulus {
+            outer: bearing_od,
+            inner: bore.max(bearing_id),
+        },
+    }
+}
+
+/// Head or nut bearing on the first or last part of the stack
+struct Loaded<'a> {
+    face: Face,
+    od: f64,
+    id: f64,
+    part: &'a Clamped,
+    chamfer: Option<f32>,
+    washer: Option<WasherFace<'a>>,
+}
+
+fn pressure(
+    face: Face,
+    on_washer: bool,
+    annulus: Annulus,
+    material: &Material,
+    part: &'static str,
+    preload: f64,
+    bolt_force: f64,
+) -> Result<BearingPressure, AnalysisError> {
+    let area = annulus.area();
+    if area <= 0.0 {
+        return Err(AnalysisError::InvalidGeometry(
+            "bearing face does not cover the hole",
+        ));
+    }
+    Ok(BearingPressure {
+        face,
+        on_washer,
+        annulus,
+        assembly: preload / area,
+        service: bolt_force / area,
+        limit: limiting_pressure(material, part)?,
+        estimated: material.scalar(Property::LimitingPressure).is_none(),
+    })
+}
+
+/// Surface pressures on every loaded face of the stack (VDI 2230 R10)
+///
+/// `washers` gives the washer under the head and under the nut. The washer itself is
+/// checked under the head or nut; the part under it sees the washer's larger annulus.
+pub fn bearing_pressures(
+    joint: &ResolvedJoint<'_>,
+    washers: [Option<WasherFace<'_>>; 2],
+    preload: f64,
+    bolt_force: f64,
+) -> Result<Vec<BearingPressure>, AnalysisError> {
+    let (Some(top), Some(bottom)) = (joint.clamped.first(), joint.clamped.last()) else {
+        return Err(AnalysisError::InvalidGeometry("no clamped parts"));
+    };
+    let [head_washer, nut_washer] = washers;
+
+    let mut faces = Vec::new();
+    match (&joint.fastener, &joint.counterpart) {
+        // A countersunk head bears on its cone, checked over the area projected onto the
+        // part, and the countersink replaces the chamfer
+        (Fastener::Bolt(bolt), counterpart) => {
+            faces.push(Loaded {
+                face: Face::Head,
+                od: bolt.bearing_face(),
+                id: 0.0,
+                part: top,
+                chamfer: match bolt.head_type {
+                    HeadType::Countersunk => None,
+                    _ => top.chamfer_top,
+                },
+                washer: head_washer,
+            });
+            if let Counterpart::Nut(nut) = counterpart {
+                faces.push(Loaded {
+                    face: Face::Nut,
+                    od: nut.bearing_od as f64,
+                    id: nut.bearing_id as f64,
+                    part: bottom,
+                    chamfer: bottom.chamfer_bottom,
+                    washer: nut_washer,
+                });
+            }
+        }
+        (Fastener::Stud(_), Counterpart::Nut(nut)) => faces.push(Loaded {
+            face: Face::Head,
+            od: nut.bearing_od as f64,
+            id: nut.bearing_id as f64,
+            part: top,
+            chamfer: top.chamfer_top,
+            washer: head_washer,
+        }),
+        (Fastener::Stud(_), Counterpart::Threaded(_)) => {
+            return Err(AnalysisError::InvalidGeometry("a stud requires a nut"));
+        }
+    }
+
+    let mut pressures = Vec::new();
+    for loaded in faces {
+        let chamfer = loaded.chamfer.map(f64::from);
+        if let Some(washer) = &loaded.washer {
+            // Head or nut on the washer
+            pressures.push(pressure(
+                loaded.face,
+                true,
+                annulus(loaded.od, loaded.id, washer.id, None, None),
+                washer.material,
+                "Washer",
+                preload,
+                bolt_force,
+            )?);
+        }
+        pressures.push(pressure(
+            loaded.face,
+            false,
+            annulus(
+                loaded.od,
+                loaded.id,
+                loaded.part.id as f64,
+                chamfer,
+                loaded.washer.as_ref(),
+            ),
+            &loaded.part.material,
+            "Clamped part",
+            preload,
+            bolt_force,
+        )?);
+    }
+    Ok(pressures)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::{
+        elements::{Bolt, Nut},
+        material::MaterialType,
+    };
+
+    fn metal(tensile_strength: f64, limiting_pressure: Option<f64>) -> Material {
+        let mut material = Material::new("Metal", MaterialType::Metal);
+        material.tensile_strength = Some(tensile_strength);
+        material.limiting_pressure = limiting_pressure;
+        material
+    }
+
+    #[test]
+    fn chamfer_and_washer_change_the_annulus() {
+        let plain = annulus(17.0, 0.0, 13.5, None, None);
+        let chamfered = annulus(17.0, 0.0, 13.5, Some(15.0), None);
+        assert!(chamfered.area() < plain.area());
+
+        let steel = metal(800e6, None);
+        let washer = WasherFace {
+            id: 13.0,
+            od: 24.0,
+            material: &steel,
+        };
+        let spread = annulus(17.0, 0.0, 13.5, Some(15.0), Some(&washer));
+        assert_eq!(
+            spread,
+            Annulus {
+                outer: 24.0,
+                inner: 15.0
+            }
+        );
+    }
+
+    #[test]
+    fn magnesium_under_the_nut_is_flagged() {
+        let bolt = Bolt {
+            bearing_od: 17.0,
+            ..Default::default()
+        };
+        let nut = Nut {
+            bearing_id: 13.0,
+            bearing_od: 17.0,
+            ..Default::default()
+        };
+        let steel = Clamped {
+            id: 13.5,
+            thickness: 10.0,
+            material: metal(700e6, None),
+            ..Default::default()
+        };
+        let magnesium = Clamped {
+            id: 13.5,
+            thickness: 10.0,
+            material: metal(250e6, Some(140e6)),
+            ..Default::default()
+        };
+        let joint = ResolvedJoint {
+            fastener: Fastener::Bolt(&bolt),
+            counterpart: Counterpart::Nut(&nut),
+            clamped: vec![&steel, &magnesium],
+            anchor: None,
+            washers: [None, None],
+            eccentricity: None,
+        };
+
+        let pressures = bearing_pressures(&joint, [None, None], 44_000.0, 46_000.0).unwrap();
+        let head = &pressures[0];
+        let under_nut = &pressures[1];
+        assert_eq!(under_nut.face, Face::Nut);
+        assert!(head.assembly <= head.limit);
+        assert!(under_nut.service > under_nut.limit);
+        assert_eq!(under_nut.limit, 140.0);
+    }
+}
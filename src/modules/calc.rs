@@ -0,0 +1,284 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::modules::{
+    analysis::{self, Analysis, AnalysisError, LoadCase, Tightening},
+    joint::{BoltedJoint, ReferenceError},
+    library::Library,
+    library_file::{self, FileError, Format},
+    pattern::{self, BoltPattern, PatternLoads},
+    sweep::Sweep,
+};
+
+/// The joint to analyse, by name from the library or given in full
+#[derive(serde::Deserialize, serde::Serialize, Clone)]
+#[serde(untagged)]
+pub enum JointRef {
+    Named(String),
+    Inline(BoltedJoint),
+}
+
+/// Calculation input file, JSON or TOML
+///
+/// ```toml
+/// library = "parts.toml"
+/// joint = "Bracket"
+///
+/// [load]
+/// axial_max = 8000.0
+/// ```
+#[derive(serde::Deserialize, serde::Serialize, Clone)]
+pub struct CalcInput {
+    pub library: PathBuf, // library file, relative to the input file
+    pub joint: JointRef,
+    #[serde(default)]
+    pub tightening: Tightening,
+    #[serde(default)]
+    pub load: LoadCase,
+    #[serde(default)]
+    pub pattern: Option<BoltPattern>, // None analyses the joint as a single bolt
+    #[serde(default)]
+    pub pattern_loads: PatternLoads,
+    #[serde(default)]
+    pub sweep: Sweep, // only read by `bjs sweep`
+}
+
+#[derive(Debug)]
+pub enum CalcError {
+    File(FileError),
+    NoJoint(String), // no joint of that name in the library
+    Reference(ReferenceError),
+    Analysis(AnalysisError),
+}
+
+impl fmt::Display for CalcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::File(error) => error.fmt(f),
+            Self::NoJoint(name) => write!(f, "the library has no joint named \"{name}\""),
+            Self::Reference(error) => write!(f, "joint is incomplete: {error}"),
+            Self::Analysis(error) => error.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for CalcError {}
+
+impl From<FileError> for CalcError {
+    fn from(error: FileError) -> Self {
+        Self::File(error)
+    }
+}
+
+/// An input file with its library loaded
+#[derive(Clone)]
+pub struct Case {
+    pub library: Library,
+    pub joint: BoltedJoint,
+    pub tightening: Tightening,
+    pub load: LoadCase,
+    pub pattern: Option<BoltPattern>,
+    pub pattern_loads: PatternLoads,
+    pub sweep: Sweep,
+}
+
+/// Reads an input file and the library it names
+pub fn load(path: &Path) -> Result<Case, CalcError> {
+    let text = std::fs::read_to_string(path).map_err(FileError::from)?;
+    let parse = |e: &dyn fmt::Display| CalcError::File(FileError::Parse(e.to_string()));
+    let input: CalcInput = match Format::of(path) {
+        Some(Format::Json) => serde_json::from_str(&text).map_err(|e| parse(&e))?,
+        Some(Format::Toml) => toml::from_str(&text).map_err(|e| parse(&e))?,
+        None => {
+            return Err(FileError::UnknownFormat(path.display().to_string()).into());
+        }
+    };
+    let library_path = path.parent().unwrap_or(Path::new("")).join(&input.library);
+    let library = library_file::load(&library_path)?.library;
+    let joint = match input.joint {
+        JointRef::Inline(joint) => joint,
+        JointRef::Named(name) => library
+            .joints()
+            .iter()
+            .find(|j| j.name == name)
+            .cloned()
+            .ok_or(CalcError::NoJoint(name))?,
+    };
+    Ok(Case {
+        library,
+        joint,
+        tightening: input.tightening,
+        load: input.load,
+        pattern: input.pattern,
+        pattern_loads: input.pattern_loads,
+        sweep: input.sweep,
+    })
+}
+
+impl Case {
+    /// Analyses the joint, through its bolt pattern when it has one
+    pub fn analyse(&self) -> Result<Analysis, CalcError> {
+        let joint = self
+            .joint
+            .resolve(&self.library)
+            .map_err(CalcError::Reference)?;
+        match &self.pattern {
+            Some(pattern) => pattern::analyse_pattern(
+                &joint,
+                &self.tightening,
+                &self.load,
+                pattern,
+                &self.pattern_loads,
+            )
+            .map(|pattern| pattern.analysis),
+            None => analysis::analyse(&joint, &self.tightening, &self.load),
+        }
+        .map_err(CalcError::Analysis)
+    }
+}
+
+/// One result value for printing
+#[derive(serde::Serialize, Debug, Clone)]
+pub struct Row {
+    pub symbol: &'static str,
+    pub value: f64,
+    pub unit: &'static str,
+}
+
+/// One check for printing
+#[derive(serde::Serialize, Debug, Clone)]
+pub struct CheckRow {
+    pub id: &'static str,
+    pub description: &'static str,
+    pub value: f64,
+    pub limit: f64,
+    pub passed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warning: Option<&'static str>,
+}
+
+/// The headline results and every check of an analysis
+#[derive(serde::Serialize, Debug, Clone)]
+pub struct Report {
+    pub passed: bool,
+    pub results: Vec<Row>,
+    pub checks: Vec<CheckRow>,
+}
+
+impl Report {
+    /// A headline result by its symbol
+    pub fn value(&self, symbol: &str) -> Option<f64> {
+        self.results
+            .iter()
+            .find(|row| row.symbol == symbol)
+            .map(|row| row.value)
+    }
+
+    pub fn new(analysis: &Analysis) -> Self {
+        let row = |symbol, value, unit| Row {
+            symbol,
+            value,
+            unit,
+        };
+        Self {
+            passed: analysis.passed(),
+            results: vec![
+                row("l_K", analysis.clamp_length, "mm"),
+                row("Φ", analysis.load_factor, ""),
+                row("F_Kerf", analysis.clamp_load_required, "N"),
+                row("F_Z", analysis.embedding_loss, "N"),
+                row("ΔF_Vth", analysis.thermal_loss, "N"),
+                row("F_Mmin", analysis.preload_min, "N"),
+                row("F_Mmax", analysis.preload_max, "N"),
+                row("F_Mzul", analysis.preload_permissible, "N"),
+                row("F_SA", analysis.additional_bolt_force, "N"),
+                row("F_Smax", analysis.bolt_force_max, "N"),
+                row("σ_redB", analysis.working_stress, "MPa"),
+                row("σ_a", analysis.alternating_stress, "MPa"),
+                row("σ_AS", analysis.endurance_limit, "MPa"),
+                row("p_max", analysis.surface_pressure, "MPa"),
+                row("F_KRmin", analysis.residual_clamp_load, "N"),
+                row("M_A", analysis.tightening_torque, "N·m"),
+            ],
+            checks: analysis
+                .checks
+                .iter()
+                .map(|c| CheckRow {
+                    id: c.id,
+                    description: c.description,
+                    value: c.value,
+                    limit: c.limit,
+                    passed: c.passed,
+                    warning: c.warning,
+                })
+                .collect(),
+        }
+    }
+
+    /// Aligned plain text table
+    pub fn table(&self) -> String {
+        let mut out = String::new();
+        for row in &self.results {
+            out += &format!("{:<8} {:>12.3} {}\n", row.symbol, row.value, row.unit);
+        }
+        out += "\n";
+        for check in &self.checks {
+            let verdict = if check.passed { "pass" } else { "FAIL" };
+            out += &format!(
+                "{:<4} {:<40} {:>12.3} {:>12.3}  {verdict}",
+                check.id, check.description, check.value, check.limit
+            );
+            if let Some(warning) = check.warning {
+                out += &format!("  ({warning})");
+            }
+            out += "\n";
+        }
+        out
+    }
+
+    pub fn json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    /// Results then checks, one record per line
+    pub fn csv(&self) -> String {
+        let mut out = String::from("kind,id,description,value,limit,unit,passed,warning\n");
+        for row in &self.results {
+            out += &format!("result,{},,{},,{},,\n", row.symbol, row.value, row.unit);
+        }
+        for check in &self.checks {
+            out += &format!(
+                "check,{},\"{}\",{},{},,{},{}\n",
+                check.id,
+                check.description,
+                check.value,
+                check.limit,
+                check.passed,
+                check.warning.map_or(String::new(), |w| format!("\"{w}\""))
+            );
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn input_names_a_joint_or_gives_it() {
+        let named: CalcInput = toml::from_str(
+            "library = \"parts.toml\"\njoint = \"Bracket\"\n[load]\naxial_max = 8000.0\n",
+        )
+        .unwrap();
+        assert!(matches!(named.joint, JointRef::Named(ref name) if name == "Bracket"));
+        assert_eq!(named.load.axial_max, 8000.0);
+        assert_eq!(named.load.load_introduction, 0.5);
+
+        let inline: CalcInput = toml::from_str(
+            "library = \"parts.toml\"\n[joint]\nname = \"Inline\"\nbolt_id = 1\nclamped_ids = [2]\n",
+        )
+        .unwrap();
+        assert!(matches!(inline.joint, JointRef::Inline(ref j) if j.bolt_id.is_some()));
+    }
+}
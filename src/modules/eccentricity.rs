@@ -0,0 +1,213 @@
+use std::f64::consts::PI;
+
+use crate::modules::resilience::{BoltResilience, PlateResilience};
+
+/// Off-axis clamping and loading of a single bolt, all dimensions in mm (VDI 2230 5.3.2)
+///
+/// Distances are measured from the axis of the substitute deformation body and are
+/// positive towards the edge at risk of opening.
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, Copy, PartialEq)]
+pub struct Eccentricity {
+    pub clamping: f64, // s_sym, bolt axis offset
+    pub load: f64,     // a, line of action of F_A
+    pub width: f64,    // b, substitute body width across the bending plane
+    pub depth: f64,    // c_T, substitute body depth in the bending plane
+}
+
+impl Default for Eccentricity {
+    fn default() -> Self {
+        Self {
+            clamping: 0.0,
+            load: 0.0,
+            width: 40.0,
+            depth: 40.0,
+        }
+    }
+}
+
+impl Eccentricity {
+    /// Bending moment of inertia of the prismatic substitute body I_Bers (mm⁴)
+    pub fn bending_inertia(&self) -> f64 {
+        self.width * self.depth.powi(3) / 12.0
+    }
+
+    /// Interface area A_D around a hole (mm²)
+    pub fn interface_area(&self, hole_diameter: f64) -> f64 {
+        self.width * self.depth - PI / 4.0 * hole_diameter.powi(2)
+    }
+
+    /// Substitute body axis to the opening edge u (mm)
+    pub fn edge(&self) -> f64 {
+        self.depth / 2.0
+    }
+}
+
+/// Eccentric load factor, opening limit and bending of the bolt (N, mm, MPa)
+#[derive(Debug, Clone)]
+pub struct Eccentric {
+    pub bending_inertia: f64,    // I_Bers (mm⁴)
+    pub bending_resilience: f64, // β_P = l_K / (E_P·I_Bers) (1/(N·mm))
+    pub plate_clamping: f64,     // δ*_P, eccentrically clamped (mm/N)
+    pub plate_loading: f64,      // δ**_P, eccentrically clamped and loaded (mm/N)
+    pub load_factor: f64,        // Φ*_en
+    pub opening_limit: f64,      // F_Kab
+    pub bending_length: f64,     // l_ers, bolt length with the minor diameter's stiffness
+    pub bending_factor: f64,     // σ_SAb / σ_SA
+    pub bending_stress: f64,     // σ_SAb at F_Amax
+}
+
+/// Length of a minor diameter bar bending like the bolt, l_ers = Σ l_i·(A_3/A_i)²
+fn bending_length(bolt: &BoltResilience, minor_area: f64) -> f64 {
+    bolt.segments
+        .iter()
+        .map(|s| s.length * (minor_area / s.area).powi(2))
+        .sum()
+}
+
+/// Eccentric clamping and loading of a bolt in a prismatic substitute body
+///
+/// Φ*_en = n·δ**_P / (δ_S + δ*_P), F_Kab from the gap opening at the edge u, and the
+/// bolt bending stress σ_SAb from the tilt of the clamped parts. `stress_diameter`
+/// is d_S, `minor_area` A_3 and `stress_area` A_S.
+#[allow(clippy::too_many_arguments)]
+pub fn eccentric(
+    eccentricity: &Eccentricity,
+    bolt: &BoltResilience,
+    plates: &PlateResilience,
+    bolt_modulus: f64,
+    load_introduction: f64,
+    hole_diameter: f64,
+    stress_diameter: f64,
+    minor_area: f64,
+    stress_area: f64,
+    axial_max: f64,
+) -> Eccentric {
+    let (s_sym, a) = (eccentricity.clamping, eccentricity.load);
+    let clamp_length = plates.cone.clamp_length;
+    // Series modulus of the stack
+    let plate_modulus = clamp_length
+        / plates
+            .layers
+            .iter()
+            .map(|l| l.thickness / l.modulus)
+            .sum::<f64>();
+    let bending_inertia = eccentricity.bending_inertia();
+    let beta_p = clamp_length / (plate_modulus * bending_inertia);
+
+    let (delta_s, delta_p) = (bolt.total(), plates.total());
+    let plate_clamping = delta_p + s_sym.powi(2) * beta_p;
+    let plate_loading = delta_p + a * s_sym * beta_p;
+    let load_factor = load_introduction * plate_loading / (delta_s + plate_clamping);
+
+    let u = eccentricity.edge();
+    let area = eccentricity.interface_area(hole_diameter);
+    let opening_limit =
+        (axial_max * (a - s_sym) * u / (bending_inertia / area + s_sym * u)).max(0.0);
+
+    // (1/Φ*_en - s_sym/a)·a written without dividing by a, so concentric loads work
+    let bending_length = bending_length(bolt, minor_area);
+    let bending_factor = 1.0
+        + (a / load_factor - s_sym)
+            * (clamp_length / bending_length)
+            * (bolt_modulus / plate_modulus)
+            * PI
+            * stress_diameter.powi(3)
+            / (8.0 * bending_inertia);
+
+    Eccentric {
+        bending_inertia,
+        bending_resilience: beta_p,
+        plate_clamping,
+        plate_loading,
+        load_factor,
+        opening_limit,
+        bending_length,
+        bending_factor,
+        bending_stress: bending_factor * load_factor * axial_max / stress_area,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::{
+        joint::JointType,
+        resilience::{DeformationCone, LayerResilience, Spring},
+    };
+
+    fn springs() -> (BoltResilience, PlateResilience) {
+        let bolt = BoltResilience {
+            segments: vec![Spring {
+                name: "Shank",
+                symbol: "δ_1",
+                length: 40.0,
+                area: 76.2,
+                modulus: 205e3,
+            }],
+        };
+        let plates = PlateResilience {
+            layers: vec![LayerResilience {
+                depth: 0.0,
+                thickness: 40.0,
+                modulus: 205e3,
+                resilience: 0.4 * bolt.total(),
+            }],
+            cone: DeformationCone {
+                joint_type: JointType::ThroughBolt,
+                bearing_diameter: 17.0,
+                outer_diameter: f64::INFINITY,
+                limit_diameter: 40.0,
+                clamp_length: 40.0,
+                tan_phi: 0.6,
+            },
+        };
+        (bolt, plates)
+    }
+
+    #[test]
+    fn concentric_reduces_to_the_plain_load_factor() {
+        let (bolt, plates) = springs();
+        let result = eccentric(
+            &Eccentricity::default(),
+            &bolt,
+            &plates,
+            205e3,
+            0.5,
+            13.5,
+            10.4,
+            76.2,
+            84.3,
+            10_000.0,
+        );
+        let phi = 0.5 * plates.total() / (bolt.total() + plates.total());
+        assert!((result.load_factor - phi).abs() < 1e-12);
+        assert_eq!(result.opening_limit, 0.0);
+        assert_eq!(result.bending_factor, 1.0);
+    }
+
+    #[test]
+    fn load_outside_the_bolt_opens_the_joint() {
+        let (bolt, plates) = springs();
+        let eccentricity = Eccentricity {
+            clamping: 5.0,
+            load: 15.0,
+            ..Default::default()
+        };
+        let result = eccentric(
+            &eccentricity,
+            &bolt,
+            &plates,
+            205e3,
+            0.5,
+            13.5,
+            10.4,
+            76.2,
+            84.3,
+            10_000.0,
+        );
+        // Loading beyond the bolt raises Φ and bends the bolt
+        assert!(result.plate_loading > result.plate_clamping);
+        assert!(result.opening_limit > 0.0);
+        assert!(result.bending_factor > 1.0);
+    }
+}
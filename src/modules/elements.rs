@@ -1,56 +1,187 @@
-use crate::modules::{material::Material, thread::Thread};
+use crate::modules::{fatigue::Rolling, material::Material, thread::Thread};
 
-#[derive(serde::Deserialize, serde::Serialize, Default)]
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, Default)]
 pub struct Clamped {
-    id: f32,
-    od: Option<f32>,
-    thickness: f32,
-    material: Material,
+    pub id: f32,
+    pub od: Option<f32>,
+    pub thickness: f32,
+    pub chamfer_top: Option<f32>, // hole chamfer diameter on the head side face
+    pub chamfer_bottom: Option<f32>, // hole chamfer diameter on the nut side face
+    pub friction: Option<f32>,    // μ_T against the next part towards the nut side
+    pub material: Material,
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Default)]
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, Default)]
 pub struct Threaded {
-    thread: Thread,
-    thread_length: f32,
-    stud_bearing: Option<f32>,
+    pub thread: Thread,
+    pub thread_length: f32,
+    pub stud_bearing: Option<f32>,
+    #[serde(default)]
+    pub material: Material,
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Default)]
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, Default)]
 pub struct Nut {
-    thread: Thread,
-    bearing_id: f32,
-    bearing_od: f32,
-    thickness: f32,
-    prev_trq: Option<f32>,
-    mass_on: Option<f32>,
-    drive: DriveType,
+    pub thread: Thread,
+    pub bearing_id: f32,
+    pub bearing_od: f32,
+    pub thickness: f32,
+    pub prev_trq: Option<f32>,
+    pub mass_on: Option<f32>,
+    pub drive: DriveType,
+    #[serde(default)]
+    pub width_across_flats: Option<f32>, // s, also sets the nut dilation of R11
+    #[serde(default)]
+    pub material: Material,
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Default)]
-enum DriveType {
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, Default)]
+pub enum DriveType {
     #[default]
     Hex,
     BiHex,
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Default)]
+/// Washer styles, which set how many interfaces settle (VDI 2230 table 5)
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WasherType {
+    #[default]
+    Plain, // ISO 7089 / 7090, 200 HV
+    Hardened,  // ISO 7089 / 7090, 300 HV
+    Spherical, // DIN 6319 C and D pair
+    Conical,   // DIN 6319 C and G pair
+    WedgeLock, // Nord-Lock style wedge locking pair
+}
+
+impl WasherType {
+    pub fn name(&self) -> &'static str {
+        match self {
+            WasherType::Plain => "Plain",
+            WasherType::Hardened => "Hardened",
+            WasherType::Spherical => "Spherical",
+            WasherType::Conical => "Conical",
+            WasherType::WedgeLock => "Wedge locking",
+        }
+    }
+
+    /// Interfaces the washer adds to the stack, pairs settle between their halves too
+    pub fn interfaces(&self) -> usize {
+        match self {
+            WasherType::Plain | WasherType::Hardened => 1,
+            WasherType::Spherical | WasherType::Conical | WasherType::WedgeLock => 2,
+        }
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, Default)]
+pub struct Washer {
+    pub kind: WasherType,
+    pub id: f32,
+    pub od: f32,
+    pub thickness: f32, // both halves of a pair
+    pub material: Material,
+}
+
+impl Washer {
+    /// The washer as a layer of the clamped stack; it does not limit the deformation cone
+    pub fn as_layer(&self) -> Clamped {
+        Clamped {
+            id: self.id,
+            thickness: self.thickness,
+            material: self.material.clone(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Bolt head types
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HeadType {
+    #[default]
+    Hex,
+    HexFlange,
+    SocketCap,
+    Countersunk,
+    Pan,
+    Button,
+    Other,
+}
+
+impl HeadType {
+    /// Internal drive heads seat a cone or a narrow face instead of a hexagon
+    pub fn internal_drive(&self) -> bool {
+        !matches!(self, HeadType::Hex | HeadType::HexFlange)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            HeadType::Hex => "Hex",
+            HeadType::HexFlange => "Hex flange",
+            HeadType::SocketCap => "Socket cap",
+            HeadType::Countersunk => "Countersunk",
+            HeadType::Pan => "Pan",
+            HeadType::Button => "Button",
+            HeadType::Other => "Other",
+        }
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, Default)]
 pub struct Stud {
-    thread_a: Thread,
-    thread_length_a: f32,
-    thread_b: Thread,
-    thread_length_b: f32,
-    shank_diameter: f32,
-    shank_length: f32,
-    nipple_id: f32,
-    nipple_od: f32,
-    nipple_angle: f32,
-}
-
-#[derive(serde::Deserialize, serde::Serialize, Default)]
+    pub thread_a: Thread,
+    pub thread_length_a: f32,
+    pub thread_b: Thread,
+    pub thread_length_b: f32,
+    pub shank_diameter: f32,
+    pub shank_length: f32,
+    pub nipple_id: f32,
+    pub nipple_od: f32,
+    pub nipple_angle: f32,
+    #[serde(default)]
+    pub rolling: Rolling,
+    #[serde(default)]
+    pub material: Material,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, Default)]
 pub struct Bolt {
-    thread: Thread,
-    thread_length: f32,
-    head_thickness: f32,
-    bearing_od: f32,
-    root_fillet: Option<f32>,
+    pub thread: Thread,
+    #[serde(default)]
+    pub length: f32,
+    pub thread_length: f32,
+    #[serde(default)]
+    pub head_type: HeadType,
+    pub head_thickness: f32, // k
+    #[serde(default)]
+    pub head_diameter: f32, // d_k, the flange d_c or across corners of a hex
+    pub bearing_od: f32,     // d_w, mean cone diameter of a countersunk head
+    pub root_fillet: Option<f32>,
+    #[serde(default)]
+    pub drive_size: Option<f32>, // s of the hex or socket
+    #[serde(default)]
+    pub rolling: Rolling,
+    #[serde(default)]
+    pub material: Material,
+}
+
+impl Bolt {
+    /// Depth below the top of the stack where a countersunk head loads the part, half
+    /// way down its cone; zero for a head on a flat face
+    pub fn sunk_depth(&self) -> f64 {
+        match self.head_type {
+            HeadType::Countersunk => self.head_thickness as f64 / 2.0,
+            _ => 0.0,
+        }
+    }
+
+    /// Outer diameter of the face the head bears on: the flange of a flange head, the
+    /// cone projected onto the part for a countersunk head, otherwise d_w
+    pub fn bearing_face(&self) -> f64 {
+        match self.head_type {
+            HeadType::HexFlange | HeadType::Countersunk if self.head_diameter > 0.0 => {
+                self.head_diameter as f64
+            }
+            _ => self.bearing_od as f64,
+        }
+    }
 }
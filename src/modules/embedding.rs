@@ -0,0 +1,182 @@
+use crate::modules::joint::{Counterpart, Fastener, ResolvedJoint};
+
+const MM_PER_UM: f64 = 1e-3;
+
+/// Embedding per (thread, bearing face, inner interface) in μm
+type Amounts = (f64, f64, f64);
+
+/// Guide values for embedding per interface, VDI 2230 table 5
+///
+/// Rows are the upper Rz limit of the band, then the amounts for axial and transverse loading.
+#[rustfmt::skip]
+const TABLE_5: &[(f64, Amounts, Amounts)] = &[
+    (10.0,  (3.0, 2.5, 1.5), (3.0, 3.0, 2.0)),
+    (40.0,  (3.0, 3.0, 2.0), (3.0, 4.5, 2.5)),
+    (160.0, (3.0, 4.0, 3.0), (3.0, 6.5, 3.5)),
+];
+
+/// Dominant direction of the working load on the interfaces
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LoadDirection {
+    #[default]
+    Axial,
+    Transverse,
+}
+
+/// Interfaces that settle in a joint
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Interfaces {
+    pub threads: usize,  // engaged threads
+    pub bearings: usize, // head or nut against a clamped part
+    pub inner: usize,    // between clamped parts, including a tapped base part
+}
+
+impl Interfaces {
+    /// Counts the interfaces of a resolved joint, clamped stack listed from the head side
+    ///
+    /// A washer adds the interface to the part it sits on, a washer pair also the one
+    /// between its halves.
+    pub fn of(joint: &ResolvedJoint<'_>) -> Self {
+        let layers = joint.clamped.len();
+        let washers: usize = joint
+            .washers
+            .iter()
+            .flatten()
+            .map(|w| w.kind.interfaces())
+            .sum();
+        let interfaces = match (&joint.fastener, &joint.counterpart) {
+            (Fastener::Bolt(_), Counterpart::Nut(_)) => Self {
+                threads: 1,
+                bearings: 2,
+                inner: layers.saturating_sub(1),
+            },
+            (Fastener::Bolt(_), Counterpart::Threaded(_)) => Self {
+                threads: 1,
+                bearings: 1,
+                inner: layers,
+            },
+            // Anchored end in the base part plus the nut thread
+            (Fastener::Stud(_), _) => Self {
+                threads: 2,
+                bearings: 1,
+                inner: layers,
+            },
+        };
+        Self {
+            inner: interfaces.inner + washers,
+            ..interfaces
+        }
+    }
+}
+
+/// Embedding amount f_Z estimated from the interfaces (μm and mm)
+#[derive(Debug, Clone)]
+pub struct Settling {
+    pub interfaces: Interfaces,
+    pub thread: f64,  // per thread interface (μm)
+    pub bearing: f64, // per bearing face (μm)
+    pub inner: f64,   // per inner interface (μm)
+}
+
+impl Settling {
+    /// Total embedding f_Z (mm)
+    pub fn total(&self) -> f64 {
+        let i = &self.interfaces;
+        (i.threads as f64 * self.thread
+            + i.bearings as f64 * self.bearing
+            + i.inner as f64 * self.inner)
+            * MM_PER_UM
+    }
+}
+
+/// Looks up the table 5 guide values, rougher surfaces than Rz 160 use the last band
+pub fn settling(joint: &ResolvedJoint<'_>, roughness: f64, direction: LoadDirection) -> Settling {
+    let (_, axial, transverse) = TABLE_5
+        .iter()
+        .find(|(limit, _, _)| roughness < *limit)
+        .unwrap_or(&TABLE_5[TABLE_5.len() - 1]);
+    let (thread, bearing, inner) = match direction {
+        LoadDirection::Axial => *axial,
+        LoadDirection::Transverse => *transverse,
+    };
+    Settling {
+        interfaces: Interfaces::of(joint),
+        thread,
+        bearing,
+        inner,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::elements::{Bolt, Clamped, Nut, Threaded, Washer, WasherType};
+
+    #[test]
+    fn interfaces_follow_the_joint() {
+        let bolt = Bolt::default();
+        let nut = Nut::default();
+        let threaded = Threaded::default();
+        let plate = Clamped::default();
+
+        let through = ResolvedJoint {
+            fastener: Fastener::Bolt(&bolt),
+            counterpart: Counterpart::Nut(&nut),
+            clamped: vec![&plate, &plate, &plate],
+            anchor: None,
+            washers: [None, None],
+            eccentricity: None,
+        };
+        let tapped = ResolvedJoint {
+            fastener: Fastener::Bolt(&bolt),
+            counterpart: Counterpart::Threaded(&threaded),
+            clamped: vec![&plate],
+            anchor: None,
+            washers: [None, None],
+            eccentricity: None,
+        };
+        assert_eq!(
+            Interfaces::of(&through),
+            Interfaces {
+                threads: 1,
+                bearings: 2,
+                inner: 2
+            }
+        );
+        assert_eq!(
+            Interfaces::of(&tapped),
+            Interfaces {
+                threads: 1,
+                bearings: 1,
+                inner: 1
+            }
+        );
+
+        // Rz 16 μm, axial: 3 + 2·3 + 2·2 = 13 μm
+        let axial = settling(&through, 16.0, LoadDirection::Axial);
+        assert!((axial.total() - 0.013).abs() < 1e-12);
+        let transverse = settling(&through, 16.0, LoadDirection::Transverse);
+        assert!(transverse.total() > axial.total());
+    }
+
+    #[test]
+    fn washers_add_interfaces() {
+        let bolt = Bolt::default();
+        let nut = Nut::default();
+        let plate = Clamped::default();
+        let plain = Washer::default();
+        let wedge = Washer {
+            kind: WasherType::WedgeLock,
+            ..Default::default()
+        };
+        let joint = ResolvedJoint {
+            fastener: Fastener::Bolt(&bolt),
+            counterpart: Counterpart::Nut(&nut),
+            clamped: vec![&plate],
+            anchor: None,
+            washers: [Some(&plain), Some(&wedge)],
+            eccentricity: None,
+        };
+        assert_eq!(Interfaces::of(&joint).inner, 3);
+    }
+}
@@ -0,0 +1,162 @@
+use std::f64::consts::PI;
+
+use crate::modules::{analysis::Section, material::Material};
+
+/// Shear strength ratio τ_B/R_m assumed when a material does not give one (steel)
+pub const DEFAULT_SHEAR_RATIO: f64 = 0.6;
+
+/// Nut dilation applies between these width across flats ratios s/d
+const NUT_DILATION_RANGE: (f64, f64) = (1.4, 1.9);
+
+/// Shear strength ratio τ_B/R_m of a material, VDI 2230 table 6
+pub fn shear_ratio(material: &Material) -> f64 {
+    material.shear_ratio.unwrap_or(DEFAULT_SHEAR_RATIO)
+}
+
+/// Thread stripping assessment of one engagement (VDI 2230 R11, after Alexander)
+#[derive(Debug, Clone)]
+pub struct Stripping {
+    pub strength_ratio: f64,   // R_s = τ_BM·A_SGM / (τ_BS·A_SGS)
+    pub dilation: f64,         // C1, nut dilation, 1 for tapped holes
+    pub bolt_bending: f64,     // C2, bolt thread bending
+    pub internal_bending: f64, // C3, internal thread bending
+    pub breaking_force: f64,   // F_mS = R_mS·A_s (N)
+    pub internal_length: f64,  // m for the internal thread to outlast the bolt (mm)
+    pub bolt_length: f64,      // m for the bolt thread to outlast the bolt (mm)
+    pub engaged: f64,          // m_eff available (mm)
+}
+
+impl Stripping {
+    /// Critical engagement length m_eff,min (mm)
+    pub fn required(&self) -> f64 {
+        self.internal_length.max(self.bolt_length)
+    }
+
+    /// Whether the bolt breaks before either thread strips
+    pub fn bolt_fails_first(&self) -> bool {
+        self.engaged >= self.required()
+    }
+
+    /// Stripping force of the weaker thread at the available engagement (N)
+    pub fn stripping_force(&self) -> f64 {
+        self.breaking_force * self.engaged / self.required()
+    }
+}
+
+/// Shear area per millimetre of engagement of the internal thread A_SGM / m
+fn internal_shear(section: &Section) -> f64 {
+    PI * section.d / section.p * (section.p / 2.0 + (section.d - section.d2) * section.flank.tan())
+}
+
+/// Shear area per millimetre of engagement of the bolt thread A_SGS / m
+fn bolt_shear(section: &Section) -> f64 {
+    PI * section.d_1 / section.p
+        * (section.p / 2.0 + (section.d2 - section.d_1) * section.flank.tan())
+}
+
+/// Nut dilation factor C1 from the width across flats, 1 for a solid housing
+fn dilation(section: &Section, nut_width: Option<f64>) -> f64 {
+    match nut_width {
+        Some(s) => {
+            let ratio = (s / section.d).clamp(NUT_DILATION_RANGE.0, NUT_DILATION_RANGE.1);
+            -ratio.powi(2) + 3.8 * ratio - 2.61
+        }
+        None => 1.0,
+    }
+}
+
+/// Bolt thread bending factor C2
+fn bolt_bending(strength_ratio: f64) -> f64 {
+    let r = strength_ratio.min(2.2);
+    if r <= 1.0 {
+        0.897
+    } else {
+        5.594 - 13.682 * r + 14.107 * r.powi(2) - 6.057 * r.powi(3) + 0.9353 * r.powi(4)
+    }
+}
+
+/// Internal thread bending factor C3
+fn internal_bending(strength_ratio: f64) -> f64 {
+    let r = strength_ratio.max(0.4);
+    if r >= 1.0 {
+        0.897
+    } else {
+        0.728 + 1.769 * r - 2.896 * r.powi(2) + 1.296 * r.powi(3)
+    }
+}
+
+/// Engagement lengths at which each thread carries the bolt's breaking force
+///
+/// Strengths are R_m in MPa. `nut_width` is the nut's width across flats, None for a
+/// tapped hole in a large part.
+pub fn stripping(
+    section: &Section,
+    bolt_tensile: f64,
+    bolt_shear_ratio: f64,
+    internal_tensile: f64,
+    internal_shear_ratio: f64,
+    nut_width: Option<f64>,
+    engaged: f64,
+) -> Stripping {
+    let tau_bs = bolt_shear_ratio * bolt_tensile;
+    let tau_bm = internal_shear_ratio * internal_tensile;
+    let a_sgm = internal_shear(section);
+    let a_sgs = bolt_shear(section);
+    let strength_ratio = tau_bm * a_sgm / (tau_bs * a_sgs);
+
+    let c1 = dilation(section, nut_width);
+    let c2 = bolt_bending(strength_ratio);
+    let c3 = internal_bending(strength_ratio);
+    // A waisted shank breaks before the thread
+    let breaking_force = bolt_tensile * section.min_area();
+
+    Stripping {
+        strength_ratio,
+        dilation: c1,
+        bolt_bending: c2,
+        internal_bending: c3,
+        breaking_force,
+        internal_length: breaking_force / (c1 * c3 * tau_bm * a_sgm),
+        bolt_length: breaking_force / (c1 * c2 * tau_bs * a_sgs),
+        engaged,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::thread::{Thread, ThreadHand};
+
+    fn m10() -> Section {
+        Section::from_thread(&Thread::new_metric(
+            10.0,
+            1.5,
+            None,
+            ThreadHand::Right,
+            None,
+        ))
+    }
+
+    #[test]
+    fn aluminium_needs_more_engagement_than_cast_iron() {
+        let section = m10();
+        // 10.9 bolt into AlSi9Cu3 (R_m 240, τ/R_m 0.7) and EN-GJL-250 (R_m 250, τ/R_m 0.9)
+        let aluminium = stripping(&section, 1040.0, 0.62, 240.0, 0.7, None, 10.0);
+        let cast_iron = stripping(&section, 1040.0, 0.62, 250.0, 0.9, None, 10.0);
+
+        assert!(aluminium.required() > cast_iron.required());
+        assert!(aluminium.required() > section.d);
+        assert!(!aluminium.bolt_fails_first());
+        assert!(aluminium.stripping_force() < aluminium.breaking_force);
+    }
+
+    #[test]
+    fn matched_nut_strips_the_bolt_thread_last() {
+        let section = m10();
+        // 8.8 bolt with a class 8 nut, s = 16 mm
+        let nut = stripping(&section, 800.0, 0.6, 800.0, 0.6, Some(16.0), 8.4);
+        assert!(nut.strength_ratio > 1.0);
+        assert!(nut.dilation < 1.0);
+        assert!(nut.required() < section.d);
+    }
+}
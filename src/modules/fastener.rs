@@ -1,18 +1,6 @@
-use crate::modules::{material::Material, thread::Thread};
+use crate::modules::{elements::HeadType, material::Material, thread::Thread};
 use std::rc::Rc;
 
-/// Bolt head types
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum HeadType {
-    Hex,
-    HexFlange,
-    SocketCap,
-    Countersunk,
-    Pan,
-    Button,
-    Other,
-}
-
 /// Bolt drive types
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DriveType {
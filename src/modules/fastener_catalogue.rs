@@ -0,0 +1,540 @@
+use std::fmt;
+
+use crate::modules::{
+    elements::{Bolt, HeadType, Nut},
+    thread::{Thread, Unit},
+    thread_catalogue::{self, DesignationError},
+};
+
+const MM_PER_INCH: f64 = 25.4;
+
+/// Head dimensions of one size, (d, k, d_k, d_w, r, s) in mm or inches
+///
+/// k head height, d_k head diameter, d_w bearing face diameter, r underhead fillet and
+/// s the width across flats of a hex or the socket size.
+type HeadRow = (f64, f64, f64, f64, f64, f64);
+
+/// Nut dimensions of one size, (d, m, s, d_w, d_a) in mm or inches
+type NutRow = (f64, f64, f64, f64, f64);
+
+/// ISO 4014 / 4017 hexagon head, product grade A
+const ISO_4014: &[HeadRow] = &[
+    (3.0, 2.0, 6.01, 4.57, 0.1, 5.5),
+    (4.0, 2.8, 7.66, 5.88, 0.2, 7.0),
+    (5.0, 3.5, 8.79, 6.88, 0.2, 8.0),
+    (6.0, 4.0, 11.05, 8.88, 0.25, 10.0),
+    (8.0, 5.3, 14.38, 11.63, 0.4, 13.0),
+    (10.0, 6.4, 17.77, 14.63, 0.4, 16.0),
+    (12.0, 7.5, 20.03, 16.63, 0.6, 18.0),
+    (16.0, 10.0, 26.75, 22.49, 0.6, 24.0),
+    (20.0, 12.5, 33.53, 28.19, 0.8, 30.0),
+    (24.0, 15.0, 39.98, 33.61, 0.8, 36.0),
+    (30.0, 18.7, 50.85, 42.75, 1.0, 46.0),
+    (36.0, 22.5, 60.79, 51.11, 1.0, 55.0),
+];
+
+/// ISO 4762 hexagon socket head cap screws
+const ISO_4762: &[HeadRow] = &[
+    (3.0, 3.0, 5.5, 5.07, 0.1, 2.5),
+    (4.0, 4.0, 7.0, 6.53, 0.2, 3.0),
+    (5.0, 5.0, 8.5, 8.03, 0.2, 4.0),
+    (6.0, 6.0, 10.0, 9.38, 0.25, 5.0),
+    (8.0, 8.0, 13.0, 12.33, 0.4, 6.0),
+    (10.0, 10.0, 16.0, 15.33, 0.4, 8.0),
+    (12.0, 12.0, 18.0, 17.23, 0.6, 10.0),
+    (16.0, 16.0, 24.0, 23.17, 0.6, 14.0),
+    (20.0, 20.0, 30.0, 28.87, 0.8, 17.0),
+    (24.0, 24.0, 36.0, 34.81, 0.8, 19.0),
+    (30.0, 30.0, 45.0, 43.61, 1.0, 22.0),
+    (36.0, 36.0, 54.0, 52.54, 1.0, 27.0),
+];
+
+/// ISO 10642 hexagon socket countersunk head screws, 90° head
+///
+/// d_k is the theoretical sharp edge diameter and d_w the mean diameter of the cone.
+const ISO_10642: &[HeadRow] = &[
+    (3.0, 1.86, 6.72, 4.86, 0.1, 2.0),
+    (4.0, 2.48, 8.96, 6.48, 0.2, 2.5),
+    (5.0, 3.1, 11.2, 8.1, 0.2, 3.0),
+    (6.0, 3.72, 13.44, 9.72, 0.25, 4.0),
+    (8.0, 4.96, 17.92, 12.96, 0.4, 5.0),
+    (10.0, 6.2, 22.4, 16.2, 0.4, 6.0),
+    (12.0, 7.44, 26.88, 19.44, 0.6, 8.0),
+    (16.0, 8.8, 33.6, 24.8, 0.6, 10.0),
+    (20.0, 10.16, 40.32, 30.16, 0.8, 12.0),
+];
+
+/// ISO 4162 hexagon flange bolts, d_k the flange diameter d_c
+const ISO_4162: &[HeadRow] = &[
+    (5.0, 5.4, 11.8, 9.8, 0.2, 8.0),
+    (6.0, 6.6, 14.2, 12.2, 0.25, 10.0),
+    (8.0, 8.1, 18.0, 15.8, 0.4, 13.0),
+    (10.0, 9.2, 22.3, 19.6, 0.4, 15.0),
+    (12.0, 11.5, 26.6, 23.8, 0.6, 18.0),
+    (16.0, 14.7, 35.0, 31.9, 0.6, 24.0),
+    (20.0, 17.9, 43.0, 39.9, 0.8, 30.0),
+];
+
+/// ASME B18.2.1 hex cap screws (in), the bearing face taken as 0.95·F
+const ASME_HEX_CAP: &[HeadRow] = &[
+    (0.25, 0.163, 0.505, 0.416, 0.01, 0.4375),
+    (0.3125, 0.211, 0.577, 0.475, 0.01, 0.5),
+    (0.375, 0.243, 0.650, 0.534, 0.01, 0.5625),
+    (0.4375, 0.291, 0.722, 0.594, 0.01, 0.625),
+    (0.5, 0.323, 0.866, 0.713, 0.01, 0.75),
+    (0.625, 0.403, 1.083, 0.891, 0.02, 0.9375),
+    (0.75, 0.483, 1.299, 1.069, 0.02, 1.125),
+    (0.875, 0.563, 1.516, 1.247, 0.04, 1.3125),
+    (1.0, 0.627, 1.732, 1.425, 0.04, 1.5),
+];
+
+/// ISO 4032 style 1 hexagon nuts
+const ISO_4032: &[NutRow] = &[
+    (3.0, 2.4, 5.5, 4.6, 3.45),
+    (4.0, 3.2, 7.0, 5.9, 4.6),
+    (5.0, 4.7, 8.0, 6.9, 5.75),
+    (6.0, 5.2, 10.0, 8.9, 6.75),
+    (8.0, 6.8, 13.0, 11.6, 8.75),
+    (10.0, 8.4, 16.0, 14.6, 10.8),
+    (12.0, 10.8, 18.0, 16.6, 13.0),
+    (16.0, 14.8, 24.0, 22.5, 17.3),
+    (20.0, 18.0, 30.0, 27.7, 21.6),
+    (24.0, 21.5, 36.0, 33.3, 25.9),
+    (30.0, 25.6, 46.0, 42.8, 32.4),
+    (36.0, 31.0, 55.0, 51.1, 38.9),
+];
+
+/// ISO 4033 style 2 hexagon nuts, taller than style 1 on the same hexagon
+const ISO_4033: &[NutRow] = &[
+    (5.0, 5.1, 8.0, 6.9, 5.75),
+    (6.0, 5.7, 10.0, 8.9, 6.75),
+    (8.0, 7.5, 13.0, 11.6, 8.75),
+    (10.0, 9.3, 16.0, 14.6, 10.8),
+    (12.0, 12.0, 18.0, 16.6, 13.0),
+    (16.0, 16.4, 24.0, 22.5, 17.3),
+    (20.0, 20.3, 30.0, 27.7, 21.6),
+    (24.0, 23.9, 36.0, 33.3, 25.9),
+    (30.0, 28.6, 46.0, 42.8, 32.4),
+    (36.0, 34.7, 55.0, 51.1, 38.9),
+];
+
+/// ASME B18.2.2 hex nuts (in), the bearing face taken as 0.95·F
+const ASME_HEX_NUT: &[NutRow] = &[
+    (0.25, 0.219, 0.4375, 0.416, 0.25),
+    (0.3125, 0.266, 0.5, 0.475, 0.3125),
+    (0.375, 0.328, 0.5625, 0.534, 0.375),
+    (0.4375, 0.375, 0.6875, 0.653, 0.4375),
+    (0.5, 0.438, 0.75, 0.713, 0.5),
+    (0.625, 0.547, 0.9375, 0.891, 0.625),
+    (0.75, 0.641, 1.125, 1.069, 0.75),
+    (0.875, 0.75, 1.3125, 1.247, 0.875),
+    (1.0, 0.859, 1.5, 1.425, 1.0),
+];
+
+/// Bolt and screw standards with catalogued heads
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoltStandard {
+    Iso4014,    // hexagon head bolt, partly threaded
+    Iso4017,    // hexagon head screw, fully threaded
+    Iso4762,    // hexagon socket head cap screw
+    Iso10642,   // hexagon socket countersunk head screw
+    Iso4162,    // hexagon flange bolt
+    AsmeHexCap, // ASME B18.2.1 hex cap screw
+}
+
+/// Nut standards with catalogued dimensions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NutStandard {
+    Iso4032,    // style 1
+    Iso4033,    // style 2
+    AsmeHexNut, // ASME B18.2.2
+}
+
+impl BoltStandard {
+    pub const ALL: [Self; 6] = [
+        Self::Iso4014,
+        Self::Iso4017,
+        Self::Iso4762,
+        Self::Iso10642,
+        Self::Iso4162,
+        Self::AsmeHexCap,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Iso4014 => "ISO 4014",
+            Self::Iso4017 => "ISO 4017",
+            Self::Iso4762 => "ISO 4762",
+            Self::Iso10642 => "ISO 10642",
+            Self::Iso4162 => "ISO 4162",
+            Self::AsmeHexCap => "ASME B18.2.1",
+        }
+    }
+
+    fn table(&self) -> &'static [HeadRow] {
+        match self {
+            Self::Iso4014 | Self::Iso4017 => ISO_4014,
+            Self::Iso4762 => ISO_4762,
+            Self::Iso10642 => ISO_10642,
+            Self::Iso4162 => ISO_4162,
+            Self::AsmeHexCap => ASME_HEX_CAP,
+        }
+    }
+
+    pub fn head_type(&self) -> HeadType {
+        match self {
+            Self::Iso4762 => HeadType::SocketCap,
+            Self::Iso10642 => HeadType::Countersunk,
+            Self::Iso4162 => HeadType::HexFlange,
+            _ => HeadType::Hex,
+        }
+    }
+
+    /// Thread length b for a nominal length, both in the table's unit
+    fn thread_length(&self, d: f64, length: f64) -> f64 {
+        let b = match self {
+            Self::Iso4014 | Self::Iso4162 if length <= 125.0 => 2.0 * d + 6.0,
+            Self::Iso4014 | Self::Iso4162 if length <= 200.0 => 2.0 * d + 12.0,
+            Self::Iso4014 | Self::Iso4162 => 2.0 * d + 25.0,
+            Self::Iso4017 => length,
+            Self::Iso4762 | Self::Iso10642 => 2.0 * d + 12.0,
+            Self::AsmeHexCap if length <= 6.0 => 2.0 * d + 0.25,
+            Self::AsmeHexCap => 2.0 * d + 0.5,
+        };
+        b.min(length)
+    }
+}
+
+impl NutStandard {
+    pub const ALL: [Self; 3] = [Self::Iso4032, Self::Iso4033, Self::AsmeHexNut];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Iso4032 => "ISO 4032",
+            Self::Iso4033 => "ISO 4033",
+            Self::AsmeHexNut => "ASME B18.2.2",
+        }
+    }
+
+    fn table(&self) -> &'static [NutRow] {
+        match self {
+            Self::Iso4032 => ISO_4032,
+            Self::Iso4033 => ISO_4033,
+            Self::AsmeHexNut => ASME_HEX_NUT,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FastenerError {
+    UnknownStandard(String),
+    MissingLength(String),
+    Thread(DesignationError),
+    UnknownSize(String),
+}
+
+impl fmt::Display for FastenerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownStandard(text) => write!(f, "\"{text}\" names no catalogued standard"),
+            Self::MissingLength(text) => write!(f, "\"{text}\" needs a length, e.g. M10x40"),
+            Self::Thread(error) => error.fmt(f),
+            Self::UnknownSize(text) => write!(f, "\"{text}\" is not a catalogued size"),
+        }
+    }
+}
+
+impl std::error::Error for FastenerError {}
+
+fn same(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-6
+}
+
+/// Splits the standard from the rest of a designation, case insensitively
+fn split_standard<S: Copy>(
+    text: &str,
+    standards: &[S],
+    name: impl Fn(&S) -> &'static str,
+) -> Result<(S, String), FastenerError> {
+    let upper = text.trim().to_uppercase();
+    standards
+        .iter()
+        .find_map(|s| {
+            upper
+                .strip_prefix(name(s))
+                .map(|rest| (*s, rest.trim().to_string()))
+        })
+        .ok_or_else(|| FastenerError::UnknownStandard(text.trim().to_string()))
+}
+
+/// Inch length as a decimal, whole or fraction ("2", "1.5", "3/4", "1-1/2", "1 1/2")
+fn inch_length(text: &str) -> Option<f64> {
+    let fraction = |text: &str| -> Option<f64> {
+        match text.split_once('/') {
+            Some((n, d)) => Some(n.trim().parse::<f64>().ok()? / d.trim().parse::<f64>().ok()?),
+            None => text.trim().parse().ok(),
+        }
+    };
+    match text.trim().split_once(['-', ' ']) {
+        Some((whole, rest)) if rest.contains('/') => {
+            Some(whole.trim().parse::<f64>().ok()? + fraction(rest)?)
+        }
+        _ => fraction(text),
+    }
+}
+
+/// Bolt from a designation such as "ISO 4762 M10x40" or "ASME B18.2.1 1/2-13 UNC x 2"
+///
+/// The length follows the last "x", overall for a countersunk head and under the head
+/// otherwise. Dimensions are stored in mm, the material is left for the property class
+/// to fill.
+pub fn bolt(designation: &str) -> Result<Bolt, FastenerError> {
+    let (standard, rest) = split_standard(designation, &BoltStandard::ALL, BoltStandard::name)?;
+    let (thread, length) = rest
+        .rsplit_once('X')
+        .filter(|(thread, _)| !is_thread_alone(&rest, thread))
+        .ok_or_else(|| FastenerError::MissingLength(designation.trim().to_string()))?;
+    let thread = thread_catalogue::parse(thread).map_err(FastenerError::Thread)?;
+    let (length, scale) = match thread.unit {
+        Unit::Metric => (length.trim().parse::<f64>().ok(), 1.0),
+        Unit::Imperial => (inch_length(length), MM_PER_INCH),
+    };
+    let length = length
+        .filter(|l| *l > 0.0)
+        .ok_or_else(|| FastenerError::MissingLength(designation.trim().to_string()))?;
+
+    let &(d, k, d_k, d_w, r, s) = standard
+        .table()
+        .iter()
+        .find(|row| same(row.0, thread.major_diameter))
+        .ok_or_else(|| FastenerError::UnknownSize(designation.trim().to_string()))?;
+    let mm = |v: f64| (v * scale) as f32;
+    Ok(Bolt {
+        length: mm(length),
+        thread_length: mm(standard.thread_length(d, length)),
+        head_type: standard.head_type(),
+        head_thickness: mm(k),
+        head_diameter: mm(d_k),
+        bearing_od: mm(d_w),
+        root_fillet: Some(mm(r)),
+        drive_size: Some(mm(s)),
+        thread,
+        ..Default::default()
+    })
+}
+
+/// Whether `rest` is a thread with its pitch, as in "M12x1.5" or "Tr20x4", rather than
+/// `thread` followed by a length
+///
+/// A metric pitch finer than the coarse one is a fine thread, not a length of a bolt.
+fn is_thread_alone(rest: &str, thread: &str) -> bool {
+    let Ok(whole) = thread_catalogue::parse(rest) else {
+        return false;
+    };
+    match thread_catalogue::parse(thread) {
+        Ok(_) if whole.unit == Unit::Metric => thread_catalogue::coarse(whole.major_diameter)
+            .is_some_and(|coarse| whole.pitch < coarse.pitch),
+        Ok(_) => false,
+        Err(_) => true,
+    }
+}
+
+/// Nut from a designation such as "ISO 4032 M12" or "ASME B18.2.2 1/2-13 UNC"
+pub fn nut(designation: &str) -> Result<Nut, FastenerError> {
+    let (standard, rest) = split_standard(designation, &NutStandard::ALL, NutStandard::name)?;
+    let thread = thread_catalogue::parse(&rest).map_err(FastenerError::Thread)?;
+    let scale = match thread.unit {
+        Unit::Metric => 1.0,
+        Unit::Imperial => MM_PER_INCH,
+    };
+    let &(_, m, s, d_w, d_a) = standard
+        .table()
+        .iter()
+        .find(|row| same(row.0, thread.major_diameter))
+        .ok_or_else(|| FastenerError::UnknownSize(designation.trim().to_string()))?;
+    let mm = |v: f64| (v * scale) as f32;
+    Ok(Nut {
+        thickness: mm(m),
+        width_across_flats: Some(mm(s)),
+        bearing_od: mm(d_w),
+        bearing_id: mm(d_a),
+        thread,
+        ..Default::default()
+    })
+}
+
+/// Millimetres per unit of a thread's dimensions
+fn scale(unit: Unit) -> f64 {
+    match unit {
+        Unit::Metric => 1.0,
+        Unit::Imperial => MM_PER_INCH,
+    }
+}
+
+/// Compares a stored dimension in mm against a catalogued one
+fn same_mm(stored: f32, catalogued: f64) -> bool {
+    (stored as f64 - catalogued).abs() < 1e-3
+}
+
+/// The bolt in another thread size, keeping its length, rolling and material
+///
+/// Head dimensions come from the standard the bolt was built from, recognised by its head
+/// height and thread length, or else the first catalogued standard with its head type.
+pub fn resize_bolt(bolt: &Bolt, thread: Thread) -> Result<Bolt, FastenerError> {
+    let old = scale(bolt.thread.unit);
+    let built_from = |standard: &&BoltStandard| {
+        standard
+            .table()
+            .iter()
+            .find(|row| same(row.0, bolt.thread.major_diameter))
+            .is_some_and(|row| {
+                let length = bolt.length as f64 / old;
+                same_mm(bolt.head_thickness, row.1 * old)
+                    && same_mm(
+                        bolt.thread_length,
+                        standard.thread_length(row.0, length) * old,
+                    )
+            })
+    };
+    let has_size = |standard: &&BoltStandard| {
+        standard
+            .table()
+            .iter()
+            .any(|row| same(row.0, thread.major_diameter))
+    };
+    let standards: Vec<BoltStandard> = BoltStandard::ALL
+        .into_iter()
+        .filter(|s| s.head_type() == bolt.head_type)
+        .collect();
+    let designation = thread_catalogue::designation(&thread);
+    let standard = standards
+        .iter()
+        .find(built_from)
+        .or_else(|| standards.iter().find(has_size))
+        .ok_or_else(|| FastenerError::UnknownSize(designation.clone()))?;
+    let &(d, k, d_k, d_w, r, s) = standard
+        .table()
+        .iter()
+        .find(|row| same(row.0, thread.major_diameter))
+        .ok_or_else(|| FastenerError::UnknownSize(format!("{} {designation}", standard.name())))?;
+
+    let scale = scale(thread.unit);
+    let mm = |v: f64| (v * scale) as f32;
+    Ok(Bolt {
+        thread_length: mm(standard.thread_length(d, bolt.length as f64 / scale)),
+        head_thickness: mm(k),
+        head_diameter: mm(d_k),
+        bearing_od: mm(d_w),
+        root_fillet: Some(mm(r)),
+        drive_size: Some(mm(s)),
+        thread,
+        ..bolt.clone()
+    })
+}
+
+/// The nut in another thread size from the standard matching its height, keeping its
+/// locking features and material
+pub fn resize_nut(nut: &Nut, thread: Thread) -> Result<Nut, FastenerError> {
+    let old = scale(nut.thread.unit);
+    let row = |standard: &NutStandard, diameter: f64| {
+        standard
+            .table()
+            .iter()
+            .find(|row| same(row.0, diameter))
+            .copied()
+    };
+    let built_from = |standard: &&NutStandard| {
+        row(standard, nut.thread.major_diameter).is_some_and(|r| same_mm(nut.thickness, r.1 * old))
+    };
+    let designation = thread_catalogue::designation(&thread);
+    let (_, m, s, d_w, d_a) = NutStandard::ALL
+        .iter()
+        .find(built_from)
+        .map_or_else(
+            || {
+                NutStandard::ALL
+                    .iter()
+                    .find_map(|standard| row(standard, thread.major_diameter))
+            },
+            |standard| row(standard, thread.major_diameter),
+        )
+        .ok_or(FastenerError::UnknownSize(designation))?;
+
+    let scale = scale(thread.unit);
+    let mm = |v: f64| (v * scale) as f32;
+    Ok(Nut {
+        thickness: mm(m),
+        width_across_flats: Some(mm(s)),
+        bearing_od: mm(d_w),
+        bearing_id: mm(d_a),
+        thread,
+        ..nut.clone()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn socket_head_cap_screw() {
+        let screw = bolt("ISO 4762 M10x40").unwrap();
+        assert_eq!(screw.head_type, HeadType::SocketCap);
+        assert_eq!(screw.thread.pitch, 1.5);
+        assert_eq!((screw.length, screw.thread_length), (40.0, 32.0));
+        assert_eq!((screw.head_thickness, screw.head_diameter), (10.0, 16.0));
+        assert_eq!(screw.drive_size, Some(8.0));
+
+        // Fine pitch keeps its pitch, the length follows the last x
+        let fine = bolt("iso 4017 M12x1.5x50").unwrap();
+        assert_eq!(fine.thread.pitch, 1.5);
+        assert_eq!(fine.thread_length, 50.0);
+    }
+
+    #[test]
+    fn inch_bolt_and_nut_in_millimetres() {
+        let inch = bolt("ASME B18.2.1 1/2-13 UNC x 1-1/2").unwrap();
+        assert!((inch.length - 38.1).abs() < 1e-4);
+        assert!((inch.thread_length - 31.75).abs() < 1e-4);
+
+        let hex = nut("ASME B18.2.2 1/2-13 UNC").unwrap();
+        assert!((hex.width_across_flats.unwrap() - 19.05).abs() < 1e-4);
+    }
+
+    #[test]
+    fn designation_errors() {
+        assert!(matches!(
+            bolt("DIN 931 M10x40"),
+            Err(FastenerError::UnknownStandard(_))
+        ));
+        assert!(matches!(
+            bolt("ISO 4762 M10"),
+            Err(FastenerError::MissingLength(_))
+        ));
+        assert!(matches!(
+            bolt("ISO 4762 M12x1.5"),
+            Err(FastenerError::MissingLength(_))
+        ));
+        let fine = bolt("ISO 4762 M12x1.5x40").unwrap();
+        assert_eq!((fine.thread.pitch, fine.length), (1.5, 40.0));
+        assert!(matches!(
+            nut("ISO 4033 M3"),
+            Err(FastenerError::UnknownSize(_))
+        ));
+    }
+
+    #[test]
+    fn resized_parts_keep_their_standard() {
+        let m12 = thread_catalogue::parse("M12").unwrap();
+        let screw = resize_bolt(&bolt("ISO 4017 M10x40").unwrap(), m12.clone()).unwrap();
+        assert_eq!((screw.length, screw.thread_length), (40.0, 40.0));
+        assert_eq!(screw.head_thickness, 7.5);
+
+        let socket = resize_bolt(&bolt("ISO 4762 M10x40").unwrap(), m12.clone()).unwrap();
+        assert_eq!((socket.thread_length, socket.head_thickness), (36.0, 12.0));
+
+        let thin = resize_nut(&nut("ISO 4032 M10").unwrap(), m12.clone()).unwrap();
+        let tall = resize_nut(&nut("ISO 4033 M10").unwrap(), m12).unwrap();
+        assert!(thin.thickness < tall.thickness);
+    }
+}
@@ -0,0 +1,145 @@
+/// Cycles at the knee of the S-N curve, VDI 2230 N_D
+pub const ENDURANCE_CYCLES: f64 = 2e6;
+
+/// Fewest cycles the finite-life region is defined for
+pub const MIN_CYCLES: f64 = 1e4;
+
+/// Whether the thread was rolled before or after heat treatment
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Rolling {
+    #[default]
+    BeforeHeatTreatment, // σ_ASV, the usual case for property class bolts
+    AfterHeatTreatment, // σ_ASG, benefits from the residual compressive stress
+}
+
+/// Fatigue assessment of the bolt thread (VDI 2230 R9, stresses in MPa)
+#[derive(Debug, Clone)]
+pub struct Fatigue {
+    pub rolling: Rolling,
+    pub cycles: Option<f64>,  // N, None for endurance
+    pub amplitude: f64,       // σ_a
+    pub mean_force: f64,      // F_Sm (N)
+    pub endurance_limit: f64, // σ_ASV or σ_ASG at N_D
+    pub strength: f64,        // σ_AS or σ_AZS at N cycles
+}
+
+impl Fatigue {
+    /// Safety against fatigue S_D = σ_AS / σ_a, infinite for a static load
+    pub fn safety(&self) -> f64 {
+        if self.amplitude > 0.0 {
+            self.strength / self.amplitude
+        } else {
+            f64::INFINITY
+        }
+    }
+}
+
+/// Endurance limit of threads rolled before heat treatment, σ_ASV = 0.85·(150/d + 45)
+pub fn endurance_before(diameter: f64) -> f64 {
+    0.85 * (150.0 / diameter + 45.0)
+}
+
+/// Endurance limit of threads rolled after heat treatment, σ_ASG = (2 − F_Sm/F_0.2min)·σ_ASV
+///
+/// The mean load ratio is held to the 0.3 to 1.0 range the relation is given for.
+pub fn endurance_after(diameter: f64, mean_force: f64, yield_force: f64) -> f64 {
+    let ratio = (mean_force / yield_force).clamp(0.3, 1.0);
+    (2.0 - ratio) * endurance_before(diameter)
+}
+
+/// Fatigue strength at `cycles`, rising with slope 1/3 (SV) or 1/6 (SG) below N_D
+fn finite_life(rolling: Rolling, endurance_limit: f64, cycles: Option<f64>) -> f64 {
+    match cycles {
+        Some(n) if n < ENDURANCE_CYCLES => {
+            let exponent = match rolling {
+                Rolling::BeforeHeatTreatment => 1.0 / 3.0,
+                Rolling::AfterHeatTreatment => 1.0 / 6.0,
+            };
+            endurance_limit * (ENDURANCE_CYCLES / n.max(MIN_CYCLES)).powf(exponent)
+        }
+        _ => endurance_limit,
+    }
+}
+
+/// Alternating stress from the axial load range and its fatigue strength
+///
+/// `load_factor` is Φ_n, `bending_factor` σ_SAb/σ_SA of an eccentric joint (1 when
+/// concentric), `preload` the assembly preload the mean force is built on,
+/// `stress_area` A_s (mm²) and `yield_strength` R_p0.2 (MPa).
+#[allow(clippy::too_many_arguments)]
+pub fn fatigue(
+    rolling: Rolling,
+    cycles: Option<f64>,
+    diameter: f64,
+    stress_area: f64,
+    yield_strength: f64,
+    load_factor: f64,
+    bending_factor: f64,
+    preload: f64,
+    axial_max: f64,
+    axial_min: f64,
+) -> Fatigue {
+    let (additional_max, additional_min) = (load_factor * axial_max, load_factor * axial_min);
+    let amplitude = bending_factor * (additional_max - additional_min).abs() / (2.0 * stress_area);
+    let mean_force = preload + (additional_max + additional_min) / 2.0;
+    let endurance_limit = match rolling {
+        Rolling::BeforeHeatTreatment => endurance_before(diameter),
+        Rolling::AfterHeatTreatment => {
+            endurance_after(diameter, mean_force, yield_strength * stress_area)
+        }
+    };
+    Fatigue {
+        rolling,
+        cycles,
+        amplitude,
+        mean_force,
+        endurance_limit,
+        strength: finite_life(rolling, endurance_limit, cycles),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn endurance_limits() {
+        // M12: 0.85·(12.5 + 45) = 48.9 MPa
+        assert!((endurance_before(12.0) - 48.875).abs() < 1e-9);
+        // Mean load at 70 % of yield: 1.3·σ_ASV
+        assert!((endurance_after(12.0, 0.7, 1.0) - 1.3 * 48.875).abs() < 1e-9);
+    }
+
+    #[test]
+    fn finite_life_raises_the_strength() {
+        let endurance = fatigue(
+            Rolling::BeforeHeatTreatment,
+            None,
+            12.0,
+            84.3,
+            640.0,
+            0.2,
+            1.0,
+            40_000.0,
+            10_000.0,
+            0.0,
+        );
+        // Φ·ΔF_A / 2A_s = 0.2·10 000 / 168.6
+        assert!((endurance.amplitude - 11.862).abs() < 1e-3);
+
+        let finite = fatigue(
+            Rolling::BeforeHeatTreatment,
+            Some(2.5e5),
+            12.0,
+            84.3,
+            640.0,
+            0.2,
+            1.0,
+            40_000.0,
+            10_000.0,
+            0.0,
+        );
+        assert!((finite.strength / endurance.strength - 2.0).abs() < 1e-9);
+        assert!(finite.safety() > endurance.safety());
+    }
+}
@@ -1,10 +1,249 @@
-#[derive(serde::Deserialize, serde::Serialize, Default)]
+use std::fmt;
+
+use crate::modules::eccentricity::Eccentricity;
+use crate::modules::elements::{Bolt, Clamped, HeadType, Nut, Stud, Threaded, Washer};
+use crate::modules::library::{Library, PartId};
+
+#[derive(serde::Deserialize, serde::Serialize, Default, Clone)]
+#[serde(default)]
 pub struct BoltedJoint {
     pub name: String,
     pub description: String,
-    pub bolt_id: Option<usize>,
-    pub stud_id: Option<usize>,
-    pub nut_id: Option<usize>,
-    pub threaded_id: Option<usize>,
-    pub clamped_ids: Option<usize>,
+    pub bolt_id: Option<PartId>,
+    pub stud_id: Option<PartId>,
+    pub nut_id: Option<PartId>,
+    pub threaded_id: Option<PartId>, // tapped part, holding the anchored end of a stud
+    pub clamped_ids: Vec<PartId>,    // listed from the head side
+    pub head_washer_id: Option<PartId>, // under the head, or under the nut of a stud
+    pub nut_washer_id: Option<PartId>, // under the nut of a through bolt
+    pub eccentricity: Option<Eccentricity>, // None for concentric clamping and loading
+}
+
+/// Why a joint's references do not make a joint
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReferenceError {
+    Dangling { part: &'static str, id: PartId }, // no such part in the library
+    NoFastener,
+    BoltAndStud,
+    NoCounterpart,
+    NutAndTappedPart, // a bolt goes into one or the other
+    StudWithoutNut,
+    NoClampedParts,
+    CountersunkWasher,
+    NutWasherWithoutNut, // only a through bolt has a nut side washer
+}
+
+impl fmt::Display for ReferenceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Dangling { part, id } => write!(f, "{part} {id} is not in the library"),
+            Self::NoFastener => write!(f, "no bolt or stud"),
+            Self::BoltAndStud => write!(f, "both a bolt and a stud are set"),
+            Self::NoCounterpart => write!(f, "no nut or tapped part"),
+            Self::NutAndTappedPart => {
+                write!(f, "a bolt goes into a nut or a tapped part, not both")
+            }
+            Self::StudWithoutNut => write!(f, "a stud is tightened with a nut"),
+            Self::NoClampedParts => write!(f, "no clamped parts"),
+            Self::CountersunkWasher => write!(f, "a countersunk head takes no washer"),
+            Self::NutWasherWithoutNut => write!(f, "only a through bolt has a nut side washer"),
+        }
+    }
+}
+
+impl std::error::Error for ReferenceError {}
+
+/// A looked up reference, naming the part when it is missing
+fn found<'a, T>(
+    entry: Option<&'a T>,
+    part: &'static str,
+    id: PartId,
+) -> Result<&'a T, ReferenceError> {
+    entry.ok_or(ReferenceError::Dangling { part, id })
+}
+
+/// The loaded member passing through the clamped parts
+pub enum Fastener<'a> {
+    Bolt(&'a Bolt),
+    Stud(&'a Stud),
+}
+
+/// The internal thread the fastener is tightened into
+pub enum Counterpart<'a> {
+    Nut(&'a Nut),
+    Threaded(&'a Threaded),
+}
+
+/// Joint type, selects the deformation cone model (VDI 2230 5.1.2.1)
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JointType {
+    ThroughBolt,  // DSV, cones from both bearing faces
+    TappedThread, // ESV, single cone from the head into the tapped part
+}
+
+/// A joint with every library reference looked up
+pub struct ResolvedJoint<'a> {
+    pub fastener: Fastener<'a>,
+    pub counterpart: Counterpart<'a>,
+    pub clamped: Vec<&'a Clamped>,
+    pub anchor: Option<&'a Threaded>, // tapped part a stud's thread_a is screwed into
+    pub washers: [Option<&'a Washer>; 2], // head side and nut side, as in BoltedJoint
+    pub eccentricity: Option<Eccentricity>,
+}
+
+impl BoltedJoint {
+    /// Every part key the joint refers to
+    pub fn references(&self) -> impl Iterator<Item = PartId> + '_ {
+        [
+            self.bolt_id,
+            self.stud_id,
+            self.nut_id,
+            self.threaded_id,
+            self.head_washer_id,
+            self.nut_washer_id,
+        ]
+        .into_iter()
+        .flatten()
+        .chain(self.clamped_ids.iter().copied())
+    }
+
+    /// Looks up the joint's parts in the library
+    ///
+    /// A bolt goes into either a nut or a tapped part. A stud is always tightened with a
+    /// nut, its other end is anchored in the tapped part when one is given. Only a
+    /// through bolt has a nut side washer, and a countersunk head takes none.
+    pub fn resolve<'a>(&self, library: &'a Library) -> Result<ResolvedJoint<'a>, ReferenceError> {
+        let fastener = match (self.bolt_id, self.stud_id) {
+            (Some(id), None) => Fastener::Bolt(found(library.bolt(id), "Bolt", id)?),
+            (None, Some(id)) => Fastener::Stud(found(library.stud(id), "Stud", id)?),
+            (Some(_), Some(_)) => return Err(ReferenceError::BoltAndStud),
+            (None, None) => return Err(ReferenceError::NoFastener),
+        };
+        let nut = |id| found(library.nut(id), "Nut", id);
+        let threaded = |id| found(library.threaded(id), "Tapped part", id);
+
+        let (counterpart, anchor) = match (&fastener, self.nut_id, self.threaded_id) {
+            (Fastener::Bolt(_), Some(id), None) => (Counterpart::Nut(nut(id)?), None),
+            (Fastener::Bolt(_), None, Some(id)) => (Counterpart::Threaded(threaded(id)?), None),
+            (Fastener::Bolt(_), Some(_), Some(_)) => {
+                return Err(ReferenceError::NutAndTappedPart);
+            }
+            (Fastener::Bolt(_), None, None) => return Err(ReferenceError::NoCounterpart),
+            (Fastener::Stud(_), Some(id), anchor) => (
+                Counterpart::Nut(nut(id)?),
+                anchor.map(threaded).transpose()?,
+            ),
+            (Fastener::Stud(_), None, _) => return Err(ReferenceError::StudWithoutNut),
+        };
+
+        if self.clamped_ids.is_empty() {
+            return Err(ReferenceError::NoClampedParts);
+        }
+        let clamped = self
+            .clamped_ids
+            .iter()
+            .map(|&id| found(library.clamped(id), "Clamped part", id))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let washer = |id| found(library.washer(id), "Washer", id);
+        let head_washer = match (self.head_washer_id, &fastener) {
+            (None, _) => None,
+            (Some(_), Fastener::Bolt(bolt)) if bolt.head_type == HeadType::Countersunk => {
+                return Err(ReferenceError::CountersunkWasher);
+            }
+            (Some(id), _) => Some(washer(id)?),
+        };
+        let nut_washer = match (self.nut_washer_id, &fastener, &counterpart) {
+            (None, _, _) => None,
+            (Some(id), Fastener::Bolt(_), Counterpart::Nut(_)) => Some(washer(id)?),
+            (Some(_), _, _) => return Err(ReferenceError::NutWasherWithoutNut),
+        };
+
+        Ok(ResolvedJoint {
+            fastener,
+            counterpart,
+            clamped,
+            anchor,
+            washers: [head_washer, nut_washer],
+            eccentricity: self.eccentricity,
+        })
+    }
+}
+
+impl ResolvedJoint<'_> {
+    /// Clamped parts with the washers as extra layers, from the head side
+    ///
+    /// A countersunk head clamps the first part from half way down its cone, which
+    /// takes the place of that part's chamfer.
+    pub fn stack(&self) -> Vec<Clamped> {
+        let [head, nut] = self.washers;
+        let mut stack: Vec<Clamped> = head
+            .map(Washer::as_layer)
+            .into_iter()
+            .chain(self.clamped.iter().map(|&part| part.clone()))
+            .chain(nut.map(Washer::as_layer))
+            .collect();
+        if let (Fastener::Bolt(bolt), Some(top)) = (&self.fastener, stack.first_mut())
+            && bolt.head_type == HeadType::Countersunk
+        {
+            top.thickness = (top.thickness - bolt.sunk_depth() as f32).max(0.0);
+            top.chamfer_top = None;
+        }
+        stack
+    }
+
+    /// Thickness of the washer under the head, the depth the first part starts at
+    pub fn head_washer_thickness(&self) -> f64 {
+        self.washers[0].map_or(0.0, |w| w.thickness as f64)
+    }
+
+    /// Tapped when the fastener goes into a tapped part or is a stud anchored in one
+    pub fn joint_type(&self) -> JointType {
+        match (&self.counterpart, self.anchor) {
+            (Counterpart::Threaded(_), _) | (_, Some(_)) => JointType::TappedThread,
+            (Counterpart::Nut(_), None) => JointType::ThroughBolt,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn removed_parts_leave_dangling_references() {
+        let mut library = Library::default();
+        let bolt_id = library.insert(String::new(), Bolt::default());
+        let first = library.insert(String::new(), Clamped::default());
+        let nut_id = library.insert(String::new(), Nut::default());
+        let joint = BoltedJoint {
+            bolt_id: Some(bolt_id),
+            nut_id: Some(nut_id),
+            clamped_ids: vec![first],
+            ..Default::default()
+        };
+        assert!(joint.resolve(&library).is_ok());
+
+        // Removing the first part neither re-points the joint nor reuses its key
+        assert!(library.remove(first));
+        let second = library.insert(String::new(), Clamped::default());
+        assert_ne!(second, first);
+        assert_eq!(
+            joint.resolve(&library).err(),
+            Some(ReferenceError::Dangling {
+                part: "Clamped part",
+                id: first
+            })
+        );
+
+        let tapped = BoltedJoint {
+            threaded_id: Some(library.insert(String::new(), Threaded::default())),
+            clamped_ids: vec![second],
+            ..joint.clone()
+        };
+        assert_eq!(
+            tapped.resolve(&library).err(),
+            Some(ReferenceError::NutAndTappedPart)
+        );
+    }
 }
@@ -1,12 +1,268 @@
-use crate::modules::elements::{Bolt, Clamped, Nut, Stud, Threaded};
-use crate::modules::joint::BoltedJoint;
+use std::fmt;
 
-#[derive(serde::Deserialize, serde::Serialize, Default)]
+use crate::modules::elements::{Bolt, Clamped, Nut, Stud, Threaded, Washer};
+use crate::modules::joint::{BoltedJoint, ReferenceError};
+
+/// Stable key of a library entry, unique across every kind of part and never reused
+#[derive(
+    serde::Deserialize, serde::Serialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+#[serde(transparent)]
+pub struct PartId(u64);
+
+impl fmt::Display for PartId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
+/// A library part and the key joints refer to it by
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone)]
+pub struct Entry<T> {
+    pub id: PartId,
+    #[serde(default)]
+    pub name: String, // catalogue designation or the team's part number, empty if unnamed
+    pub part: T,
+}
+
+/// Part kinds kept in the library, for code working across every list
+pub trait Part: Clone + serde::Serialize {
+    const KIND: &'static str;
+    fn entries(library: &Library) -> &Vec<Entry<Self>>;
+    fn entries_mut(library: &mut Library) -> &mut Vec<Entry<Self>>;
+}
+
+impl Part for Bolt {
+    const KIND: &'static str = "Bolt";
+    fn entries(library: &Library) -> &Vec<Entry<Self>> {
+        &library.bolt
+    }
+    fn entries_mut(library: &mut Library) -> &mut Vec<Entry<Self>> {
+        &mut library.bolt
+    }
+}
+
+impl Part for Stud {
+    const KIND: &'static str = "Stud";
+    fn entries(library: &Library) -> &Vec<Entry<Self>> {
+        &library.stud
+    }
+    fn entries_mut(library: &mut Library) -> &mut Vec<Entry<Self>> {
+        &mut library.stud
+    }
+}
+
+impl Part for Nut {
+    const KIND: &'static str = "Nut";
+    fn entries(library: &Library) -> &Vec<Entry<Self>> {
+        &library.nut
+    }
+    fn entries_mut(library: &mut Library) -> &mut Vec<Entry<Self>> {
+        &mut library.nut
+    }
+}
+
+impl Part for Threaded {
+    const KIND: &'static str = "Tapped part";
+    fn entries(library: &Library) -> &Vec<Entry<Self>> {
+        &library.threaded
+    }
+    fn entries_mut(library: &mut Library) -> &mut Vec<Entry<Self>> {
+        &mut library.threaded
+    }
+}
+
+impl Part for Clamped {
+    const KIND: &'static str = "Clamped part";
+    fn entries(library: &Library) -> &Vec<Entry<Self>> {
+        &library.clamped
+    }
+    fn entries_mut(library: &mut Library) -> &mut Vec<Entry<Self>> {
+        &mut library.clamped
+    }
+}
+
+impl Part for Washer {
+    const KIND: &'static str = "Washer";
+    fn entries(library: &Library) -> &Vec<Entry<Self>> {
+        &library.washer
+    }
+    fn entries_mut(library: &mut Library) -> &mut Vec<Entry<Self>> {
+        &mut library.washer
+    }
+}
+
+fn find<T>(entries: &[Entry<T>], id: PartId) -> Option<&T> {
+    entries.iter().find(|e| e.id == id).map(|e| &e.part)
+}
+
+fn find_mut<T>(entries: &mut [Entry<T>], id: PartId) -> Option<&mut T> {
+    entries.iter_mut().find(|e| e.id == id).map(|e| &mut e.part)
+}
+
+/// Removes the entry with `id`, true if there was one
+fn remove<T>(entries: &mut Vec<Entry<T>>, id: PartId) -> bool {
+    let len = entries.len();
+    entries.retain(|e| e.id != id);
+    entries.len() < len
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Default)]
 pub struct Library {
-    bolt: Vec<Bolt>,
-    stud: Vec<Stud>,
-    nut: Vec<Nut>,
-    threaded: Vec<Threaded>,
-    clamped: Vec<Clamped>,
+    next_id: u64, // keys are handed out in order and not reused after a removal
+    bolt: Vec<Entry<Bolt>>,
+    stud: Vec<Entry<Stud>>,
+    nut: Vec<Entry<Nut>>,
+    threaded: Vec<Entry<Threaded>>,
+    clamped: Vec<Entry<Clamped>>,
+    #[serde(default)]
+    washer: Vec<Entry<Washer>>,
     joint: Vec<BoltedJoint>,
 }
+
+impl Library {
+    fn next(&mut self) -> PartId {
+        self.next_id += 1;
+        PartId(self.next_id)
+    }
+
+    /// Adds a named part of any kind and returns its key
+    pub fn insert<T: Part>(&mut self, name: String, part: T) -> PartId {
+        let id = self.next();
+        T::entries_mut(self).push(Entry { id, name, part });
+        id
+    }
+
+    /// Moves the next key past every key in use by a part or a joint, so a file edited by
+    /// hand cannot hand out a key twice or bring a dangling reference back to life
+    pub fn reserve_keys(&mut self, open: &BoltedJoint) {
+        fn largest<T>(entries: &[Entry<T>]) -> u64 {
+            entries.iter().map(|e| e.id.0).max().unwrap_or(0)
+        }
+        let parts = [
+            largest(&self.bolt),
+            largest(&self.stud),
+            largest(&self.nut),
+            largest(&self.threaded),
+            largest(&self.clamped),
+            largest(&self.washer),
+        ];
+        let references = self
+            .joint
+            .iter()
+            .chain([open])
+            .flat_map(BoltedJoint::references)
+            .map(|id| id.0);
+        let used = parts.into_iter().chain(references).max().unwrap_or(0);
+        self.next_id = self.next_id.max(used);
+    }
+
+    /// Copy holding only the parts with the given keys, under the same keys, and no joints
+    pub fn only(&self, ids: impl IntoIterator<Item = PartId>) -> Library {
+        let ids: Vec<PartId> = ids.into_iter().collect();
+        fn keep<T: Clone>(entries: &[Entry<T>], ids: &[PartId]) -> Vec<Entry<T>> {
+            entries
+                .iter()
+                .filter(|e| ids.contains(&e.id))
+                .cloned()
+                .collect()
+        }
+        Library {
+            next_id: self.next_id,
+            bolt: keep(&self.bolt, &ids),
+            stud: keep(&self.stud, &ids),
+            nut: keep(&self.nut, &ids),
+            threaded: keep(&self.threaded, &ids),
+            clamped: keep(&self.clamped, &ids),
+            washer: keep(&self.washer, &ids),
+            joint: Vec::new(),
+        }
+    }
+
+    /// Whether a part of any kind has the key
+    pub fn contains(&self, id: PartId) -> bool {
+        fn has<T>(entries: &[Entry<T>], id: PartId) -> bool {
+            entries.iter().any(|e| e.id == id)
+        }
+        has(&self.bolt, id)
+            || has(&self.stud, id)
+            || has(&self.nut, id)
+            || has(&self.threaded, id)
+            || has(&self.clamped, id)
+            || has(&self.washer, id)
+    }
+
+    /// Part of any kind by its key, for edits that do not depend on the kind
+    pub fn part_mut<T: Part>(&mut self, id: PartId) -> Option<&mut T> {
+        find_mut(T::entries_mut(self), id)
+    }
+
+    /// Names a part of any kind, false if the key is not in the library
+    pub fn rename(&mut self, id: PartId, name: &str) -> bool {
+        fn named<T>(entries: &mut [Entry<T>], id: PartId, name: &str) -> bool {
+            entries
+                .iter_mut()
+                .find(|e| e.id == id)
+                .map(|e| e.name = name.to_string())
+                .is_some()
+        }
+        named(&mut self.bolt, id, name)
+            || named(&mut self.stud, id, name)
+            || named(&mut self.nut, id, name)
+            || named(&mut self.threaded, id, name)
+            || named(&mut self.clamped, id, name)
+            || named(&mut self.washer, id, name)
+    }
+
+    pub fn bolt(&self, id: PartId) -> Option<&Bolt> {
+        find(&self.bolt, id)
+    }
+
+    pub fn stud(&self, id: PartId) -> Option<&Stud> {
+        find(&self.stud, id)
+    }
+
+    pub fn nut(&self, id: PartId) -> Option<&Nut> {
+        find(&self.nut, id)
+    }
+
+    pub fn threaded(&self, id: PartId) -> Option<&Threaded> {
+        find(&self.threaded, id)
+    }
+
+    pub fn clamped(&self, id: PartId) -> Option<&Clamped> {
+        find(&self.clamped, id)
+    }
+
+    pub fn washer(&self, id: PartId) -> Option<&Washer> {
+        find(&self.washer, id)
+    }
+
+    /// Removes a part of any kind, true if the key was in the library
+    ///
+    /// Joints keep their references and fail to resolve rather than pick up another part.
+    pub fn remove(&mut self, id: PartId) -> bool {
+        remove(&mut self.bolt, id)
+            || remove(&mut self.stud, id)
+            || remove(&mut self.nut, id)
+            || remove(&mut self.threaded, id)
+            || remove(&mut self.clamped, id)
+            || remove(&mut self.washer, id)
+    }
+
+    pub fn joints(&self) -> &[BoltedJoint] {
+        &self.joint
+    }
+
+    pub fn add_joint(&mut self, joint: BoltedJoint) {
+        self.joint.push(joint);
+    }
+
+    /// Stored joints that no longer resolve, with the reason
+    pub fn validate(&self) -> Vec<(&BoltedJoint, ReferenceError)> {
+        self.joint
+            .iter()
+            .filter_map(|joint| joint.resolve(self).err().map(|error| (joint, error)))
+            .collect()
+    }
+}
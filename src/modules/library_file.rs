@@ -0,0 +1,338 @@
+use std::fmt;
+use std::path::Path;
+
+use serde_json::{Map, Value};
+
+use crate::modules::{joint::BoltedJoint, library::Library};
+
+/// Schema version written to new files
+///
+/// 1. Library as persisted before stable part keys, joints holding list indices
+/// 2. Parts keyed by `PartId`, wrapped with the version and the open joint
+pub const SCHEMA_VERSION: u32 = 2;
+
+/// Part lists of a library, in the order the keys of a migrated file are handed out
+const PART_KINDS: [&str; 6] = ["bolt", "stud", "nut", "threaded", "clamped", "washer"];
+
+/// A shared library and the joint open when it was saved
+#[derive(serde::Deserialize, serde::Serialize, Default)]
+pub struct LibraryFile {
+    pub version: u32,
+    pub library: Library,
+    #[serde(default)]
+    pub joint: BoltedJoint,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    Toml,
+}
+
+impl Format {
+    /// Format from a path's extension
+    pub fn of(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_lowercase().as_str() {
+            "json" => Some(Self::Json),
+            "toml" => Some(Self::Toml),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum FileError {
+    Io(std::io::Error),
+    UnknownFormat(String), // extension other than .json or .toml
+    Parse(String),
+    Write(String),
+    Invalid(&'static str),
+    NewerVersion(u32), // written by a later release
+}
+
+impl fmt::Display for FileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => error.fmt(f),
+            Self::UnknownFormat(path) => write!(f, "{path} is not a .json or .toml file"),
+            Self::Parse(error) => write!(f, "could not read library: {error}"),
+            Self::Write(error) => write!(f, "could not write library: {error}"),
+            Self::Invalid(reason) => write!(f, "invalid library: {reason}"),
+            Self::NewerVersion(version) => write!(
+                f,
+                "library version {version} is newer than the supported {SCHEMA_VERSION}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for FileError {}
+
+impl From<std::io::Error> for FileError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+/// Migrations from each version to the next, the first taking version 1 to 2
+const MIGRATIONS: [fn(Value) -> Result<Value, FileError>; 1] = [migrate_v1];
+
+/// Version 1 to 2: keys the parts in list order and rewrites the joints' indices
+///
+/// An index past the end of its list gets a key of its own, so the joint still fails
+/// to resolve instead of picking up another part.
+fn migrate_v1(value: Value) -> Result<Value, FileError> {
+    let Value::Object(mut old) = value else {
+        return Err(FileError::Invalid("library is not a table"));
+    };
+    let mut next_id = 0;
+    let mut keys: Vec<(&str, Vec<u64>)> = Vec::new();
+    let mut library = Map::new();
+    for kind in PART_KINDS {
+        let parts = match old.remove(kind) {
+            Some(Value::Array(parts)) => parts,
+            None => Vec::new(),
+            Some(_) => return Err(FileError::Invalid("part list is not an array")),
+        };
+        let mut ids = Vec::new();
+        let entries = parts
+            .into_iter()
+            .map(|part| {
+                next_id += 1;
+                ids.push(next_id);
+                serde_json::json!({ "id": next_id, "part": part })
+            })
+            .collect();
+        library.insert(kind.to_string(), Value::Array(entries));
+        keys.push((kind, ids));
+    }
+
+    let mut rekey = |kind: &str, index: &Value| -> Value {
+        let ids = keys.iter().find(|(k, _)| *k == kind).map(|(_, ids)| ids);
+        let id = index
+            .as_u64()
+            .and_then(|i| ids?.get(i as usize).copied())
+            .unwrap_or_else(|| {
+                next_id += 1;
+                next_id
+            });
+        Value::from(id)
+    };
+    let references = [
+        ("bolt_id", "bolt"),
+        ("stud_id", "stud"),
+        ("nut_id", "nut"),
+        ("threaded_id", "threaded"),
+        ("head_washer_id", "washer"),
+        ("nut_washer_id", "washer"),
+    ];
+    let mut joints = match old.remove("joint") {
+        Some(Value::Array(joints)) => joints,
+        None => Vec::new(),
+        Some(_) => return Err(FileError::Invalid("joint list is not an array")),
+    };
+    for joint in &mut joints {
+        let Value::Object(joint) = joint else {
+            return Err(FileError::Invalid("joint is not a table"));
+        };
+        for (field, kind) in references {
+            if let Some(index) = joint.get_mut(field).filter(|i| !i.is_null()) {
+                *index = rekey(kind, index);
+            }
+        }
+        // Version 1 held at most one clamped part, as a single index or null
+        let clamped = match joint.remove("clamped_ids") {
+            None | Some(Value::Null) => Vec::new(),
+            Some(Value::Array(indices)) => indices.iter().map(|i| rekey("clamped", i)).collect(),
+            Some(index) => vec![rekey("clamped", &index)],
+        };
+        joint.insert("clamped_ids".to_string(), Value::Array(clamped));
+    }
+    library.insert("joint".to_string(), Value::Array(joints));
+    library.insert("next_id".to_string(), Value::from(next_id));
+
+    Ok(serde_json::json!({ "version": 2, "library": library }))
+}
+
+/// Brings a parsed file up to the current schema; a file without a version is version 1
+pub fn migrate(mut value: Value) -> Result<Value, FileError> {
+    let mut version = match value.get("version") {
+        Some(version) => version
+            .as_u64()
+            .ok_or(FileError::Invalid("version is not a number"))
+            .and_then(|v| {
+                u32::try_from(v).map_err(|_| FileError::Invalid("version is too large"))
+            })?,
+        None => 1,
+    };
+    if version < 1 {
+        return Err(FileError::Invalid("version is below 1"));
+    }
+    if version > SCHEMA_VERSION {
+        return Err(FileError::NewerVersion(version));
+    }
+    while version < SCHEMA_VERSION {
+        value = MIGRATIONS[version as usize - 1](value)?;
+        version += 1;
+    }
+    Ok(value)
+}
+
+pub fn from_str(text: &str, format: Format) -> Result<LibraryFile, FileError> {
+    let value: Value = match format {
+        Format::Json => serde_json::from_str(text).map_err(|e| FileError::Parse(e.to_string()))?,
+        Format::Toml => toml::from_str(text).map_err(|e| FileError::Parse(e.to_string()))?,
+    };
+    let mut file: LibraryFile =
+        serde_json::from_value(migrate(value)?).map_err(|e| FileError::Parse(e.to_string()))?;
+    file.library.reserve_keys(&file.joint);
+    Ok(file)
+}
+
+fn write(file: &impl serde::Serialize, format: Format) -> Result<String, FileError> {
+    match format {
+        Format::Json => {
+            serde_json::to_string_pretty(file).map_err(|e| FileError::Write(e.to_string()))
+        }
+        Format::Toml => toml::to_string_pretty(file).map_err(|e| FileError::Write(e.to_string())),
+    }
+}
+
+fn format(path: &Path) -> Result<Format, FileError> {
+    Format::of(path).ok_or_else(|| FileError::UnknownFormat(path.display().to_string()))
+}
+
+/// Reads a library file, migrating it from an older version
+pub fn load(path: &Path) -> Result<LibraryFile, FileError> {
+    from_str(&std::fs::read_to_string(path)?, format(path)?)
+}
+
+/// Writes a library and its open joint at the current version, the format set by the extension
+pub fn save(path: &Path, library: &Library, joint: &BoltedJoint) -> Result<(), FileError> {
+    let file = LibraryFileRef {
+        version: SCHEMA_VERSION,
+        library,
+        joint,
+    };
+    Ok(std::fs::write(path, write(&file, format(path)?)?)?)
+}
+
+/// Borrowed `LibraryFile`, so saving does not copy the library
+#[derive(serde::Serialize)]
+struct LibraryFileRef<'a> {
+    version: u32,
+    library: &'a Library,
+    joint: &'a BoltedJoint,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::elements::{Bolt, Clamped, Nut};
+
+    #[test]
+    fn round_trips_through_both_formats() {
+        let mut library = Library::default();
+        let joint = BoltedJoint {
+            name: "Flange".to_string(),
+            bolt_id: Some(library.insert(String::new(), Bolt::default())),
+            nut_id: Some(library.insert(String::new(), Nut::default())),
+            clamped_ids: vec![library.insert(String::new(), Clamped::default())],
+            ..Default::default()
+        };
+        let file = LibraryFile {
+            version: SCHEMA_VERSION,
+            library,
+            joint,
+        };
+        for format in [Format::Json, Format::Toml] {
+            let text = write(&file, format).unwrap();
+            let read = from_str(&text, format).unwrap();
+            assert_eq!(read.joint.name, "Flange");
+            assert!(read.joint.resolve(&read.library).is_ok());
+        }
+    }
+
+    /// A library as version 1 wrote it: parts without materials or lengths, and joints
+    /// pointing at list indices with at most one clamped part
+    const VERSION_1: &str = r#"{
+        "bolt": [{"thread": THREAD, "thread_length": 26.0, "head_thickness": 6.4,
+            "bearing_od": 14.6, "root_fillet": null}],
+        "stud": [],
+        "nut": [{"thread": THREAD, "bearing_id": 10.5, "bearing_od": 14.6, "thickness": 8.4,
+            "prev_trq": null, "mass_on": null, "drive": "Hex"}],
+        "threaded": [],
+        "clamped": [
+            {"id": 11.0, "od": null, "thickness": 12.0, "material": MATERIAL},
+            {"id": 11.0, "od": 40.0, "thickness": 20.0, "material": MATERIAL}
+        ],
+        "joint": [
+            {"name": "Old", "description": "", "bolt_id": 0, "stud_id": null, "nut_id": 0,
+                "threaded_id": null, "clamped_ids": 1},
+            {"name": "Bare", "description": "", "bolt_id": 3, "stud_id": null, "nut_id": null,
+                "threaded_id": null, "clamped_ids": null}
+        ]
+    }"#;
+    const THREAD: &str = r#"{"unit": "Metric", "form": "ISO", "major_diameter": 10.0,
+        "minor_diameter": 8.16, "pitch": 1.5, "threads_per_unit": null, "length": null,
+        "hand": "Right", "angle": 60.0, "tolerance_class": "6g", "note": null}"#;
+    const MATERIAL: &str = r#"{"name": "Steel", "material_type": "Metal", "standard": null,
+        "density": 7850.0, "youngs_modulus": 210000000000.0, "shear_modulus": null,
+        "poisson_ratio": 0.3, "tensile_strength": null, "yield_strength": null,
+        "hardness": null, "thermal_conductivity": null, "thermal_expansion": null,
+        "specific_heat": null, "melting_point": null, "electrical_conductivity": null,
+        "resistivity": null, "cost_per_kg": null, "note": null}"#;
+
+    fn version_1() -> String {
+        VERSION_1
+            .replace("THREAD", THREAD)
+            .replace("MATERIAL", MATERIAL)
+    }
+
+    #[test]
+    fn index_references_are_migrated() {
+        let old = version_1();
+        let value = migrate(serde_json::from_str(&old).unwrap()).unwrap();
+        assert_eq!(value["version"], SCHEMA_VERSION);
+        let joints = &value["library"]["joint"];
+        assert_eq!(
+            (&joints[0]["bolt_id"], &joints[0]["nut_id"]),
+            (&1.into(), &2.into())
+        );
+        // A single clamped index becomes a list of one, null an empty list
+        assert_eq!(joints[0]["clamped_ids"], serde_json::json!([4]));
+        assert_eq!(joints[1]["clamped_ids"], serde_json::json!([]));
+        // The missing part gets a key of its own rather than the next part's
+        assert_eq!(joints[1]["bolt_id"], 5);
+
+        // Fields added since version 1 take their defaults
+        let file = from_str(&old, Format::Json).unwrap();
+        let joints = file.library.joints();
+        assert!(joints[0].resolve(&file.library).is_ok());
+        assert!(joints[1].resolve(&file.library).is_err());
+        let bolt = file.library.bolt(joints[0].bolt_id.unwrap()).unwrap();
+        assert_eq!(bolt.thread_length, 26.0);
+        assert_eq!(bolt.material.name, "");
+    }
+
+    #[test]
+    fn bad_versions_and_stale_keys() {
+        for version in ["0", "-1", "4294967296"] {
+            let text = format!(r#"{{"version":{version},"library":{{}}}}"#);
+            assert!(matches!(
+                from_str(&text, Format::Json),
+                Err(FileError::Invalid(_))
+            ));
+        }
+
+        // A hand edited next_id below the keys in use, and a joint pointing past them
+        let text = r#"{"version":2,"library":{"next_id":1,
+            "bolt":[{"id":3,"part":BOLT}],"stud":[],"nut":[],"threaded":[],"clamped":[],
+            "joint":[{"name":"Old","description":"","bolt_id":3,"stud_id":null,"nut_id":7,
+                "threaded_id":null,"clamped_ids":[],"eccentricity":null}]}}"#
+            .replace("BOLT", &serde_json::to_string(&Bolt::default()).unwrap());
+        let mut file = from_str(&text, Format::Json).unwrap();
+        let nut = file.library.insert(String::new(), Nut::default());
+        assert_eq!(nut.to_string(), "#8");
+    }
+}
@@ -0,0 +1,356 @@
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+
+use crate::modules::{
+    elements::{Bolt, Clamped, Nut, Stud, Threaded, Washer},
+    joint::BoltedJoint,
+    library::{Library, Part, PartId},
+};
+
+/// How a part of the incoming library relates to ours
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    Added,
+    Identical(PartId), // same name and properties
+    Duplicate(PartId), // same properties under another name
+    Conflict {
+        ours: PartId,
+        fields: Vec<String>, // dotted paths of the properties that differ
+    },
+}
+
+/// Choice for a part whose name clashes with one of ours
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Resolution {
+    #[default]
+    KeepOurs,
+    TakeTheirs, // overwrite ours, so our joints pick up the incoming part
+    KeepBoth,   // add the incoming part under a suffixed name
+}
+
+#[derive(Debug, Clone)]
+pub struct Difference {
+    pub kind: &'static str,
+    pub name: String,
+    pub theirs: PartId,
+    pub change: Change,
+    pub resolution: Resolution, // only used for conflicts
+}
+
+/// What merging does with an incoming joint
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JointChange {
+    Added,
+    NameTaken,        // we have a joint of that name and keep ours
+    Dangling(PartId), // refers to a part the incoming library does not have, left out
+}
+
+/// Part by part comparison of an incoming library against ours
+#[derive(Debug, Clone, Default)]
+pub struct Diff {
+    pub parts: Vec<Difference>,
+    pub joints: Vec<(String, JointChange)>, // in the incoming library's order
+}
+
+impl Diff {
+    pub fn conflicts(&self) -> impl Iterator<Item = &Difference> {
+        self.parts
+            .iter()
+            .filter(|d| matches!(d.change, Change::Conflict { .. }))
+    }
+
+    pub fn count(&self, matches: fn(&Change) -> bool) -> usize {
+        self.parts.iter().filter(|d| matches(&d.change)).count()
+    }
+}
+
+fn to_value(part: &impl serde::Serialize) -> Value {
+    serde_json::to_value(part).unwrap_or(Value::Null)
+}
+
+/// Dotted paths of the fields that differ between two serialised parts
+fn changed_fields(ours: &Value, theirs: &Value, path: &str, fields: &mut Vec<String>) {
+    match (ours, theirs) {
+        (Value::Object(ours), Value::Object(theirs)) => {
+            let mut keys: Vec<&String> = ours.keys().chain(theirs.keys()).collect();
+            keys.sort();
+            keys.dedup();
+            for key in keys {
+                let path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{path}.{key}")
+                };
+                let missing = Value::Null;
+                changed_fields(
+                    ours.get(key).unwrap_or(&missing),
+                    theirs.get(key).unwrap_or(&missing),
+                    &path,
+                    fields,
+                );
+            }
+        }
+        (ours, theirs) if ours != theirs => fields.push(path.to_string()),
+        _ => {}
+    }
+}
+
+fn diff_parts<T: Part>(ours: &Library, theirs: &Library, parts: &mut Vec<Difference>) {
+    let ours: Vec<_> = T::entries(ours)
+        .iter()
+        .map(|e| (e.id, &e.name, to_value(&e.part)))
+        .collect();
+    for entry in T::entries(theirs) {
+        let value = to_value(&entry.part);
+        let same = ours.iter().find(|(_, _, v)| *v == value);
+        let named = ours
+            .iter()
+            .find(|(_, name, _)| !entry.name.is_empty() && **name == entry.name);
+        let change = match (same, named) {
+            (Some(&(id, name, _)), _) if *name == entry.name => Change::Identical(id),
+            (_, Some((id, _, v))) => {
+                let mut fields = Vec::new();
+                changed_fields(v, &value, "", &mut fields);
+                if fields.is_empty() {
+                    Change::Identical(*id)
+                } else {
+                    Change::Conflict { ours: *id, fields }
+                }
+            }
+            (Some(&(id, ..)), None) => Change::Duplicate(id),
+            (None, None) => Change::Added,
+        };
+        parts.push(Difference {
+            kind: T::KIND,
+            name: entry.name.clone(),
+            theirs: entry.id,
+            change,
+            resolution: Resolution::default(),
+        });
+    }
+}
+
+/// Compares every part and joint of `theirs` against `ours`
+///
+/// Parts are matched on their serialised properties first, so a renamed copy of one of
+/// our parts is a duplicate; a part sharing only the name is a conflict.
+pub fn diff(ours: &Library, theirs: &Library) -> Diff {
+    let mut parts = Vec::new();
+    diff_parts::<Bolt>(ours, theirs, &mut parts);
+    diff_parts::<Stud>(ours, theirs, &mut parts);
+    diff_parts::<Nut>(ours, theirs, &mut parts);
+    diff_parts::<Threaded>(ours, theirs, &mut parts);
+    diff_parts::<Clamped>(ours, theirs, &mut parts);
+    diff_parts::<Washer>(ours, theirs, &mut parts);
+
+    let joints = theirs
+        .joints()
+        .iter()
+        .map(|joint| {
+            let change = if ours.joints().iter().any(|j| j.name == joint.name) {
+                JointChange::NameTaken
+            } else if let Some(id) = joint.references().find(|id| !theirs.contains(*id)) {
+                JointChange::Dangling(id)
+            } else {
+                JointChange::Added
+            };
+            (joint.name.clone(), change)
+        })
+        .collect();
+    Diff { parts, joints }
+}
+
+/// "name (merged)", numbered when an earlier merge already took it
+fn merged_name<T: Part>(ours: &Library, name: &str) -> String {
+    let taken = |candidate: &str| T::entries(ours).iter().any(|e| e.name == candidate);
+    let mut candidate = format!("{name} (merged)");
+    let mut n = 1;
+    while taken(&candidate) {
+        n += 1;
+        candidate = format!("{name} (merged {n})");
+    }
+    candidate
+}
+
+fn merge_parts<T: Part>(
+    ours: &mut Library,
+    theirs: &Library,
+    diff: &Diff,
+    keys: &mut BTreeMap<PartId, PartId>,
+) {
+    for entry in T::entries(theirs) {
+        let Some(difference) = diff
+            .parts
+            .iter()
+            .find(|d| d.kind == T::KIND && d.theirs == entry.id)
+        else {
+            continue;
+        };
+        let id = match (&difference.change, difference.resolution) {
+            (Change::Identical(id) | Change::Duplicate(id), _) => *id,
+            (Change::Conflict { ours: id, .. }, Resolution::KeepOurs) => *id,
+            (Change::Conflict { ours: id, .. }, Resolution::TakeTheirs) => {
+                if let Some(ours) = T::entries_mut(ours).iter_mut().find(|e| e.id == *id) {
+                    ours.part = entry.part.clone();
+                }
+                *id
+            }
+            (Change::Conflict { .. }, Resolution::KeepBoth) => {
+                let name = merged_name::<T>(ours, &entry.name);
+                ours.insert(name, entry.part.clone())
+            }
+            (Change::Added, _) => ours.insert(entry.name.clone(), entry.part.clone()),
+        };
+        keys.insert(entry.id, id);
+    }
+}
+
+/// The joint pointed at the parts its references were merged into, None if one was not
+fn rekey(joint: &BoltedJoint, keys: &BTreeMap<PartId, PartId>) -> Option<BoltedJoint> {
+    let key = |id: Option<PartId>| match id {
+        Some(id) => keys.get(&id).map(|id| Some(*id)),
+        None => Some(None),
+    };
+    Some(BoltedJoint {
+        bolt_id: key(joint.bolt_id)?,
+        stud_id: key(joint.stud_id)?,
+        nut_id: key(joint.nut_id)?,
+        threaded_id: key(joint.threaded_id)?,
+        clamped_ids: joint
+            .clamped_ids
+            .iter()
+            .map(|id| keys.get(id).copied())
+            .collect::<Option<_>>()?,
+        head_washer_id: key(joint.head_washer_id)?,
+        nut_washer_id: key(joint.nut_washer_id)?,
+        ..joint.clone()
+    })
+}
+
+/// Merges `theirs` into `ours` following the resolutions in `diff`
+///
+/// Incoming joints are added under names we do not have yet, re-pointed at the parts
+/// they were merged into. A joint referring to a part `theirs` does not have is left
+/// out, as listed in the diff, rather than merged with that reference dropped.
+pub fn merge(ours: &mut Library, theirs: &Library, diff: &Diff) {
+    let mut keys = BTreeMap::new();
+    merge_parts::<Bolt>(ours, theirs, diff, &mut keys);
+    merge_parts::<Stud>(ours, theirs, diff, &mut keys);
+    merge_parts::<Nut>(ours, theirs, diff, &mut keys);
+    merge_parts::<Threaded>(ours, theirs, diff, &mut keys);
+    merge_parts::<Clamped>(ours, theirs, diff, &mut keys);
+    merge_parts::<Washer>(ours, theirs, diff, &mut keys);
+
+    for (joint, (_, change)) in theirs.joints().iter().zip(&diff.joints) {
+        if *change == JointChange::Added
+            && let Some(joint) = rekey(joint, &keys)
+        {
+            ours.add_joint(joint);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bolt(length: f32) -> Bolt {
+        Bolt {
+            length,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn finds_duplicates_and_conflicts() {
+        let mut ours = Library::default();
+        let standard = ours.insert("ISO 4762 M10x40".to_string(), bolt(40.0));
+        let special = ours.insert("Spacer bolt".to_string(), bolt(55.0));
+
+        let mut theirs = Library::default();
+        let copy = theirs.insert("PN-1001".to_string(), bolt(40.0));
+        theirs.insert("Spacer bolt".to_string(), bolt(60.0));
+        theirs.insert("Long bolt".to_string(), bolt(90.0));
+
+        let diff = diff(&ours, &theirs);
+        assert_eq!(diff.parts[0].theirs, copy);
+        assert_eq!(diff.parts[0].change, Change::Duplicate(standard));
+        assert_eq!(
+            diff.parts[1].change,
+            Change::Conflict {
+                ours: special,
+                fields: vec!["length".to_string()]
+            }
+        );
+        assert_eq!(diff.parts[2].change, Change::Added);
+    }
+
+    #[test]
+    fn joints_follow_merged_parts() {
+        let mut ours = Library::default();
+        let nut = ours.insert("ISO 4032 M10".to_string(), Nut::default());
+        ours.insert("Spacer bolt".to_string(), bolt(55.0));
+
+        let mut theirs = Library::default();
+        let bolt_id = theirs.insert("Spacer bolt".to_string(), bolt(60.0));
+        let nut_id = theirs.insert("PN-2001".to_string(), Nut::default());
+        let plate = theirs.insert("Plate".to_string(), Clamped::default());
+        theirs.add_joint(BoltedJoint {
+            name: "Bracket".to_string(),
+            bolt_id: Some(bolt_id),
+            nut_id: Some(nut_id),
+            clamped_ids: vec![plate],
+            ..Default::default()
+        });
+
+        let mut diff = diff(&ours, &theirs);
+        diff.parts[0].resolution = Resolution::TakeTheirs;
+        merge(&mut ours, &theirs, &diff);
+
+        let joint = &ours.joints()[0];
+        assert_eq!(joint.nut_id, Some(nut));
+        let resolved = joint.resolve(&ours).unwrap();
+        let crate::modules::joint::Fastener::Bolt(merged) = resolved.fastener else {
+            panic!("expected a bolt");
+        };
+        assert_eq!(merged.length, 60.0);
+    }
+
+    #[test]
+    fn incomplete_joints_and_repeated_copies() {
+        let mut ours = Library::default();
+        ours.insert("Spacer bolt".to_string(), bolt(55.0));
+
+        let mut theirs = Library::default();
+        theirs.insert("Spacer bolt".to_string(), bolt(60.0));
+        let plate = theirs.insert("Plate".to_string(), Clamped::default());
+        let gone = theirs.insert("Gone".to_string(), Clamped::default());
+        theirs.remove(gone);
+        theirs.add_joint(BoltedJoint {
+            name: "Thin".to_string(),
+            clamped_ids: vec![plate, gone],
+            ..Default::default()
+        });
+
+        for _ in 0..2 {
+            let mut diff = diff(&ours, &theirs);
+            assert_eq!(diff.joints[0].1, JointChange::Dangling(gone));
+            diff.parts[0].resolution = Resolution::KeepBoth;
+            merge(&mut ours, &theirs, &diff);
+        }
+        assert!(ours.joints().is_empty());
+        let names: Vec<&str> = Bolt::entries(&ours)
+            .iter()
+            .map(|e| e.name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "Spacer bolt",
+                "Spacer bolt (merged)",
+                "Spacer bolt (merged 2)"
+            ]
+        );
+    }
+}
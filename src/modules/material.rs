@@ -1,3 +1,9 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Temperature the scalar property values are quoted at (°C)
+pub const REFERENCE_TEMPERATURE: f64 = 20.0;
+
 /// Material type: metal, polymer, ceramic, etc.
 #[derive(serde::Deserialize, serde::Serialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum MaterialType {
@@ -18,6 +24,187 @@ pub struct MaterialStandard {
     pub note: Option<String>,
 }
 
+/// Scalar material properties that may also be given as a curve over temperature
+#[derive(
+    serde::Deserialize, serde::Serialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord,
+)]
+pub enum Property {
+    Density,
+    YoungsModulus,
+    ShearModulus,
+    PoissonRatio,
+    TensileStrength,
+    YieldStrength,
+    Hardness,
+    LimitingPressure,
+    ThermalConductivity,
+    ThermalExpansion,
+    SpecificHeat,
+    ElectricalConductivity,
+    Resistivity,
+}
+
+impl Property {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Property::Density => "density",
+            Property::YoungsModulus => "Young's modulus",
+            Property::ShearModulus => "shear modulus",
+            Property::PoissonRatio => "Poisson's ratio",
+            Property::TensileStrength => "tensile strength",
+            Property::YieldStrength => "yield strength",
+            Property::Hardness => "hardness",
+            Property::LimitingPressure => "limiting surface pressure",
+            Property::ThermalConductivity => "thermal conductivity",
+            Property::ThermalExpansion => "thermal expansion",
+            Property::SpecificHeat => "specific heat",
+            Property::ElectricalConductivity => "electrical conductivity",
+            Property::Resistivity => "resistivity",
+        }
+    }
+}
+
+/// What a curve returns for temperatures outside its table
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutOfRange {
+    #[default]
+    Error, // refuse to evaluate
+    Clamp,       // hold the nearest end value
+    Extrapolate, // extend the end segment linearly
+}
+
+/// Evaluating a property curve outside its temperature range
+#[derive(Debug, Clone, PartialEq)]
+pub struct RangeError {
+    pub property: Property,
+    pub temperature: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl fmt::Display for RangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} is only defined from {} °C to {} °C, not at {} °C",
+            self.property.name(),
+            self.min,
+            self.max,
+            self.temperature
+        )
+    }
+}
+
+impl std::error::Error for RangeError {}
+
+/// Why a table of points does not make a curve
+#[derive(Debug, Clone, PartialEq)]
+pub enum CurveError {
+    Empty,
+    NotFinite,
+    RepeatedTemperature(f64), // two values at one temperature
+}
+
+impl fmt::Display for CurveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "a curve needs at least one point"),
+            Self::NotFinite => write!(f, "curve points must be finite numbers"),
+            Self::RepeatedTemperature(t) => write!(f, "the curve has two values at {t} °C"),
+        }
+    }
+}
+
+impl std::error::Error for CurveError {}
+
+/// Property values tabulated against temperature, linearly interpolated
+///
+/// Read through `Curve::new`, so a curve from a file is checked like one built in code.
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, PartialEq)]
+#[serde(try_from = "CurveTable")]
+pub struct Curve {
+    points: Vec<(f64, f64)>, // (°C, value) sorted by temperature
+    pub out_of_range: OutOfRange,
+}
+
+/// A curve as stored, before its points are checked
+#[derive(serde::Deserialize)]
+struct CurveTable {
+    points: Vec<(f64, f64)>,
+    #[serde(default)]
+    out_of_range: OutOfRange,
+}
+
+impl TryFrom<CurveTable> for Curve {
+    type Error = CurveError;
+
+    fn try_from(table: CurveTable) -> Result<Self, CurveError> {
+        Curve::new(table.points, table.out_of_range)
+    }
+}
+
+impl Curve {
+    /// A curve through the given (temperature °C, value) points, in any order
+    pub fn new(mut points: Vec<(f64, f64)>, out_of_range: OutOfRange) -> Result<Self, CurveError> {
+        if points.is_empty() {
+            return Err(CurveError::Empty);
+        }
+        if points.iter().any(|(t, v)| !t.is_finite() || !v.is_finite()) {
+            return Err(CurveError::NotFinite);
+        }
+        points.sort_by(|a, b| a.0.total_cmp(&b.0));
+        if let Some(pair) = points.windows(2).find(|pair| pair[0].0 == pair[1].0) {
+            return Err(CurveError::RepeatedTemperature(pair[0].0));
+        }
+        Ok(Self {
+            points,
+            out_of_range,
+        })
+    }
+
+    /// Temperature range covered by the table (°C)
+    pub fn range(&self) -> (f64, f64) {
+        (self.points[0].0, self.points[self.points.len() - 1].0)
+    }
+
+    /// Value at a temperature, None when it lies outside the table and the curve refuses
+    pub fn at(&self, temperature: f64) -> Option<f64> {
+        let (min, max) = self.range();
+        let points = &self.points;
+        if points.len() == 1 {
+            return match self.out_of_range {
+                OutOfRange::Error if temperature != min => None,
+                _ => Some(points[0].1),
+            };
+        }
+
+        let segment = if temperature < min || temperature > max {
+            match self.out_of_range {
+                OutOfRange::Error => return None,
+                OutOfRange::Clamp => {
+                    let end = if temperature < min {
+                        points[0]
+                    } else {
+                        points[points.len() - 1]
+                    };
+                    return Some(end.1);
+                }
+                OutOfRange::Extrapolate if temperature < min => 0,
+                OutOfRange::Extrapolate => points.len() - 2,
+            }
+        } else {
+            points
+                .windows(2)
+                .position(|w| temperature <= w[1].0)
+                .unwrap_or(points.len() - 2)
+        };
+
+        let (t0, v0) = points[segment];
+        let (t1, v1) = points[segment + 1];
+        Some(v0 + (v1 - v0) * (temperature - t0) / (t1 - t0))
+    }
+}
+
 /// Material struct for engineering
 #[derive(serde::Deserialize, serde::Serialize, Debug, Clone, Default)]
 pub struct Material {
@@ -25,15 +212,18 @@ pub struct Material {
     pub name: String,                       // common name, e.g., "Steel A36"
     pub material_type: MaterialType,        // e.g., Metal
     pub standard: Option<MaterialStandard>, // optional standard
+    pub property_class: Option<String>,     // fastener class, e.g. "8.8", "A4-80"
 
     // Mechanical properties
-    pub density: Option<f64>,          // kg/m³
-    pub youngs_modulus: Option<f64>,   // Pa
-    pub shear_modulus: Option<f64>,    // Pa
-    pub poisson_ratio: Option<f64>,    // unitless
-    pub tensile_strength: Option<f64>, // Pa
-    pub yield_strength: Option<f64>,   // Pa
-    pub hardness: Option<f64>,         // Vickers, Brinell, Rockwell
+    pub density: Option<f64>,           // kg/m³
+    pub youngs_modulus: Option<f64>,    // Pa
+    pub shear_modulus: Option<f64>,     // Pa
+    pub poisson_ratio: Option<f64>,     // unitless
+    pub tensile_strength: Option<f64>,  // Pa
+    pub yield_strength: Option<f64>,    // Pa
+    pub shear_ratio: Option<f64>,       // τ_B / R_m, ultimate shear over tensile strength
+    pub hardness: Option<f64>,          // Vickers, Brinell, Rockwell
+    pub limiting_pressure: Option<f64>, // Pa, p_G under bearing faces
 
     // Thermal properties
     pub thermal_conductivity: Option<f64>, // W/(m·K)
@@ -48,6 +238,10 @@ pub struct Material {
     // Optional metadata
     pub cost_per_kg: Option<f64>, // currency/kg
     pub note: Option<String>,     // any descriptive notes
+
+    // Temperature dependence, properties without a curve are constant
+    #[serde(default)]
+    pub curves: BTreeMap<Property, Curve>,
 }
 
 impl Material {
@@ -57,13 +251,16 @@ impl Material {
             name: name.into(),
             material_type,
             standard: None,
+            property_class: None,
             density: None,
             youngs_modulus: None,
             shear_modulus: None,
             poisson_ratio: None,
             tensile_strength: None,
             yield_strength: None,
+            shear_ratio: None,
             hardness: None,
+            limiting_pressure: None,
             thermal_conductivity: None,
             thermal_expansion: None,
             specific_heat: None,
@@ -72,6 +269,7 @@ impl Material {
             resistivity: None,
             cost_per_kg: None,
             note: None,
+            curves: BTreeMap::new(),
         }
     }
 
@@ -107,6 +305,15 @@ impl Material {
         self.tensile_strength = Some(tensile_strength);
         self.yield_strength = Some(yield_strength);
         self.hardness = Some(hardness);
+        self.clear_curves(&[
+            Property::Density,
+            Property::YoungsModulus,
+            Property::ShearModulus,
+            Property::PoissonRatio,
+            Property::TensileStrength,
+            Property::YieldStrength,
+            Property::Hardness,
+        ]);
     }
 
     /// Set thermal properties
@@ -121,12 +328,92 @@ impl Material {
         self.thermal_expansion = Some(thermal_expansion);
         self.specific_heat = Some(specific_heat);
         self.melting_point = Some(melting_point);
+        self.clear_curves(&[
+            Property::ThermalConductivity,
+            Property::ThermalExpansion,
+            Property::SpecificHeat,
+        ]);
     }
 
     /// Set electrical properties
     pub fn set_electrical(&mut self, conductivity: f64, resistivity: f64) {
         self.electrical_conductivity = Some(conductivity);
         self.resistivity = Some(resistivity);
+        self.clear_curves(&[Property::ElectricalConductivity, Property::Resistivity]);
+    }
+
+    fn scalar_mut(&mut self, property: Property) -> &mut Option<f64> {
+        match property {
+            Property::Density => &mut self.density,
+            Property::YoungsModulus => &mut self.youngs_modulus,
+            Property::ShearModulus => &mut self.shear_modulus,
+            Property::PoissonRatio => &mut self.poisson_ratio,
+            Property::TensileStrength => &mut self.tensile_strength,
+            Property::YieldStrength => &mut self.yield_strength,
+            Property::Hardness => &mut self.hardness,
+            Property::LimitingPressure => &mut self.limiting_pressure,
+            Property::ThermalConductivity => &mut self.thermal_conductivity,
+            Property::ThermalExpansion => &mut self.thermal_expansion,
+            Property::SpecificHeat => &mut self.specific_heat,
+            Property::ElectricalConductivity => &mut self.electrical_conductivity,
+            Property::Resistivity => &mut self.resistivity,
+        }
+    }
+
+    /// Scalar value of a property at the reference temperature
+    pub fn scalar(&self, property: Property) -> Option<f64> {
+        match property {
+            Property::Density => self.density,
+            Property::YoungsModulus => self.youngs_modulus,
+            Property::ShearModulus => self.shear_modulus,
+            Property::PoissonRatio => self.poisson_ratio,
+            Property::TensileStrength => self.tensile_strength,
+            Property::YieldStrength => self.yield_strength,
+            Property::Hardness => self.hardness,
+            Property::LimitingPressure => self.limiting_pressure,
+            Property::ThermalConductivity => self.thermal_conductivity,
+            Property::ThermalExpansion => self.thermal_expansion,
+            Property::SpecificHeat => self.specific_heat,
+            Property::ElectricalConductivity => self.electrical_conductivity,
+            Property::Resistivity => self.resistivity,
+        }
+    }
+
+    /// Set a property as a curve over temperature, keeping the scalar at the reference temperature
+    pub fn set_curve(&mut self, property: Property, curve: Curve) {
+        let reference = Curve {
+            out_of_range: OutOfRange::Extrapolate,
+            ..curve.clone()
+        };
+        *self.scalar_mut(property) = reference.at(REFERENCE_TEMPERATURE);
+        self.curves.insert(property, curve);
+    }
+
+    fn clear_curves(&mut self, properties: &[Property]) {
+        for property in properties {
+            self.curves.remove(property);
+        }
+    }
+
+    /// Whether the property varies with temperature
+    pub fn has_curve(&self, property: Property) -> bool {
+        self.curves.contains_key(&property)
+    }
+
+    /// Property value at a temperature in °C, Ok(None) when the material does not define it
+    pub fn at(&self, property: Property, temperature: f64) -> Result<Option<f64>, RangeError> {
+        match self.curves.get(&property) {
+            Some(curve) => curve.at(temperature).map(Some).ok_or_else(|| {
+                let (min, max) = curve.range();
+                RangeError {
+                    property,
+                    temperature,
+                    min,
+                    max,
+                }
+            }),
+            None => Ok(self.scalar(property)),
+        }
     }
 
     /// Add a descriptive note
@@ -134,3 +421,73 @@ impl Material {
         self.note = Some(note.into());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn aluminium() -> Material {
+        let mut material = Material::new("EN AW-6082", MaterialType::Metal);
+        material.set_mechanical(2700.0, 70e9, 26e9, 0.33, 310e6, 260e6, 95.0);
+        material
+    }
+
+    #[test]
+    fn scalars_behave_as_constant_curves() {
+        let material = aluminium();
+        assert_eq!(material.at(Property::YoungsModulus, -40.0), Ok(Some(70e9)));
+        assert_eq!(material.at(Property::YoungsModulus, 150.0), Ok(Some(70e9)));
+        assert_eq!(material.at(Property::ThermalExpansion, 150.0), Ok(None));
+    }
+
+    #[test]
+    fn curves_interpolate_and_report_range() {
+        let mut material = aluminium();
+        let curve = Curve::new(
+            vec![(100.0, 245e6), (-50.0, 270e6), (200.0, 180e6)],
+            OutOfRange::Error,
+        )
+        .unwrap();
+        material.set_curve(Property::YieldStrength, curve);
+
+        // Scalar follows the curve at 20 °C
+        let expected = 270e6 + (245e6 - 270e6) * 70.0 / 150.0;
+        assert!((material.yield_strength.unwrap() - expected).abs() < 1.0);
+        assert_eq!(
+            material.at(Property::YieldStrength, 150.0),
+            Ok(Some(212.5e6))
+        );
+        assert_eq!(
+            material.at(Property::YieldStrength, 250.0),
+            Err(RangeError {
+                property: Property::YieldStrength,
+                temperature: 250.0,
+                min: -50.0,
+                max: 200.0,
+            })
+        );
+
+        material
+            .curves
+            .get_mut(&Property::YieldStrength)
+            .unwrap()
+            .out_of_range = OutOfRange::Clamp;
+        assert_eq!(material.at(Property::YieldStrength, 250.0), Ok(Some(180e6)));
+
+        // Scalar setters replace the curve again
+        material.set_mechanical(2700.0, 70e9, 26e9, 0.33, 310e6, 260e6, 95.0);
+        assert!(!material.has_curve(Property::YieldStrength));
+    }
+
+    #[test]
+    fn curves_from_files_are_checked() {
+        let read = |json: &str| serde_json::from_str::<Curve>(json);
+        assert!(read(r#"{"points":[],"out_of_range":"Clamp"}"#).is_err());
+        assert!(read(r#"{"points":[[20,1.0],[20,2.0]],"out_of_range":"Clamp"}"#).is_err());
+
+        let curve = read(r#"{"points":[[100,2.0],[20,1.0]],"out_of_range":"Error"}"#).unwrap();
+        assert_eq!(curve.range(), (20.0, 100.0));
+        assert_eq!(curve.at(60.0), Some(1.5));
+        assert_eq!(curve.at(120.0), None);
+    }
+}
@@ -1,9 +1,28 @@
+pub mod analysis;
+pub mod bearing;
+pub mod calc;
+pub mod eccentricity;
 pub mod elements;
+pub mod embedding;
+pub mod engagement;
 // pub mod fastener;
+pub mod fastener_catalogue;
+pub mod fatigue;
 pub mod geometry;
 pub mod joint;
 pub mod library;
+pub mod library_file;
+pub mod library_merge;
 pub mod material;
+pub mod pattern;
+pub mod property_class;
+pub mod resilience;
+pub mod slip;
 pub mod state;
+pub mod sweep;
+pub mod thermal;
 pub mod thread;
+pub mod thread_catalogue;
+pub mod torque;
 pub mod utils;
+pub mod washer_catalogue;
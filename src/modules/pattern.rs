@@ -0,0 +1,284 @@
+use std::f64::consts::PI;
+
+use crate::modules::{
+    analysis::{Analysis, AnalysisError, LoadCase, Tightening, analyse},
+    geometry::{Circle, Point},
+    joint::ResolvedJoint,
+};
+
+/// Bolt positions in the plane of the interface, z along the bolt axes (mm)
+#[derive(serde::Deserialize, serde::Serialize, Clone)]
+pub enum Layout {
+    Circular {
+        count: usize,
+        radius: f64,      // pitch circle radius
+        start_angle: f64, // degrees from +x to the first bolt
+    },
+    Rectangular {
+        rows: usize,    // along y
+        columns: usize, // along x
+        pitch_x: f64,
+        pitch_y: f64,
+    },
+    Arbitrary(Vec<Point>),
+}
+
+/// How the external moment is shared between the bolts
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, Copy, PartialEq)]
+pub enum Distribution {
+    Elastic,             // flanges deform together, tilting about the bolt group centroid
+    Rigid { edge: f64 }, // stiff flange tipping about its edge, this far from the centroid
+}
+
+/// External forces (N) and moments (N·mm) acting at the bolt group centroid
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, Copy, Default)]
+pub struct PatternLoads {
+    pub fx: f64,
+    pub fy: f64,
+    pub fz: f64, // tension along the bolt axes
+    pub mx: f64,
+    pub my: f64,
+    pub mz: f64, // torque about the bolt axes
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone)]
+pub struct BoltPattern {
+    pub layout: Layout,
+    pub distribution: Distribution,
+}
+
+impl Default for BoltPattern {
+    fn default() -> Self {
+        Self {
+            layout: Layout::Circular {
+                count: 8,
+                radius: 60.0,
+                start_angle: 22.5,
+            },
+            distribution: Distribution::Elastic,
+        }
+    }
+}
+
+/// Share of the external loads carried by one bolt
+#[derive(Clone, Copy)]
+pub struct BoltLoad {
+    pub position: Point, // relative to the bolt group centroid
+    pub axial: f64,      // F_A (N), negative in compression
+    pub shear: f64,      // F_Q (N)
+}
+
+impl BoltPattern {
+    /// Bolt positions as laid out, not yet centred
+    pub fn positions(&self) -> Vec<Point> {
+        match &self.layout {
+            Layout::Circular {
+                count,
+                radius,
+                start_angle,
+            } => (0..*count)
+                .map(|i| {
+                    let angle = start_angle.to_radians() + 2.0 * PI * i as f64 / *count as f64;
+                    Point::new(radius * angle.cos(), radius * angle.sin())
+                })
+                .collect(),
+            Layout::Rectangular {
+                rows,
+                columns,
+                pitch_x,
+                pitch_y,
+            } => (0..*rows)
+                .flat_map(|row| {
+                    (0..*columns).map(move |column| {
+                        Point::new(column as f64 * pitch_x, row as f64 * pitch_y)
+                    })
+                })
+                .collect(),
+            Layout::Arbitrary(points) => points.clone(),
+        }
+    }
+
+    /// Centroid of the bolt group, every bolt taken as the same size
+    pub fn centroid(&self) -> Point {
+        let positions = self.positions();
+        let n = positions.len().max(1) as f64;
+        let (x, y) = positions
+            .iter()
+            .fold((0.0, 0.0), |(x, y), p| (x + p.x, y + p.y));
+        Point::new(x / n, y / n)
+    }
+
+    /// Splits the external loads between the bolts
+    ///
+    /// F_z and the in-plane forces are shared equally. The bending moment tilts the
+    /// flange about a neutral axis along the moment vector, through the centroid for
+    /// `Elastic` or on the flange edge for `Rigid`, loading each bolt in proportion to
+    /// its distance from that axis. M_z is carried in shear in proportion to radius.
+    pub fn distribute(&self, loads: &PatternLoads) -> Vec<BoltLoad> {
+        let centroid = self.centroid();
+        let positions: Vec<Point> = self.positions().into_iter().map(|p| p - centroid).collect();
+        let n = positions.len() as f64;
+
+        // Unit vector pointing towards the bolts the moment puts in tension
+        let moment = loads.mx.hypot(loads.my);
+        let (ux, uy) = if moment > 0.0 {
+            (-loads.my / moment, loads.mx / moment)
+        } else {
+            (0.0, 0.0)
+        };
+        let offset = match self.distribution {
+            Distribution::Elastic => 0.0,
+            Distribution::Rigid { edge } => edge,
+        };
+        let lever = |p: &Point| p.x * ux + p.y * uy + offset;
+        let lever_sum: f64 = positions.iter().map(|p| lever(p).powi(2)).sum();
+        let polar_sum: f64 = positions.iter().map(|p| p.x * p.x + p.y * p.y).sum();
+
+        positions
+            .iter()
+            .map(|p| {
+                let bending = if lever_sum > 0.0 {
+                    moment * lever(p) / lever_sum
+                } else {
+                    0.0
+                };
+                let (torsion_x, torsion_y) = if polar_sum > 0.0 {
+                    (-loads.mz * p.y / polar_sum, loads.mz * p.x / polar_sum)
+                } else {
+                    (0.0, 0.0)
+                };
+                BoltLoad {
+                    position: *p,
+                    axial: loads.fz / n + bending,
+                    shear: (loads.fx / n + torsion_x).hypot(loads.fy / n + torsion_y),
+                }
+            })
+            .collect()
+    }
+
+    /// Bolt holes around the centroid for plotting, plus the pitch circle of a circular layout
+    pub fn circles(&self, hole_diameter: f64) -> Vec<Circle> {
+        let centroid = self.centroid();
+        let mut circles: Vec<Circle> = self
+            .positions()
+            .into_iter()
+            .map(|p| Circle::new(p - centroid, hole_diameter / 2.0))
+            .collect();
+        if let Layout::Circular { radius, .. } = self.layout {
+            circles.push(Circle::new(Point::new(0.0, 0.0), radius));
+        }
+        circles
+    }
+}
+
+/// Index of the bolt with the highest axial load, the highest shear breaking ties
+pub fn critical(bolts: &[BoltLoad]) -> Option<usize> {
+    bolts
+        .iter()
+        .enumerate()
+        .max_by(|(_, a), (_, b)| {
+            a.axial
+                .total_cmp(&b.axial)
+                .then(a.shear.total_cmp(&b.shear))
+        })
+        .map(|(i, _)| i)
+}
+
+/// Bolt loads of a pattern and the single bolt analysis of the critical one
+pub struct PatternAnalysis {
+    pub bolts: Vec<BoltLoad>,
+    pub critical: usize,
+    pub analysis: Analysis,
+}
+
+/// Distributes the loads, picks the critical bolt and analyses it with `load` as the
+/// base case
+///
+/// The critical bolt's axial load becomes F_Amax and its shear F_Qmax, with M_z already
+/// turned into shear so no torsion is passed on.
+pub fn analyse_pattern(
+    joint: &ResolvedJoint<'_>,
+    tightening: &Tightening,
+    load: &LoadCase,
+    pattern: &BoltPattern,
+    loads: &PatternLoads,
+) -> Result<PatternAnalysis, AnalysisError> {
+    let bolts = pattern.distribute(loads);
+    let critical =
+        critical(&bolts).ok_or(AnalysisError::InvalidGeometry("bolt pattern has no bolts"))?;
+
+    let bolt = bolts[critical];
+    let axial_max = bolt.axial.max(0.0);
+    let load = LoadCase {
+        axial_max,
+        axial_min: load.axial_min.min(axial_max),
+        transverse: bolt.shear,
+        torsion: 0.0,
+        ..load.clone()
+    };
+    let analysis = analyse(joint, tightening, &load)?;
+
+    Ok(PatternAnalysis {
+        bolts,
+        critical,
+        analysis,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn moment_on_a_bolt_circle() {
+        let pattern = BoltPattern {
+            layout: Layout::Circular {
+                count: 4,
+                radius: 50.0,
+                start_angle: 0.0,
+            },
+            distribution: Distribution::Elastic,
+        };
+        let loads = PatternLoads {
+            fz: 4_000.0,
+            mx: 1e6,
+            mz: 2e5,
+            ..Default::default()
+        };
+        let bolts = pattern.distribute(&loads);
+        // M_x loads the bolt at +y by M/(Σy²)·y = 1e6/5000·50
+        assert!((bolts[1].axial - (1_000.0 + 10_000.0)).abs() < 1e-9);
+        assert!((bolts[3].axial - (1_000.0 - 10_000.0)).abs() < 1e-9);
+        assert!((bolts[0].axial - 1_000.0).abs() < 1e-9);
+        // M_z shared equally on a circle, M/(n·r)
+        assert!(bolts.iter().all(|b| (b.shear - 1_000.0).abs() < 1e-9));
+    }
+
+    #[test]
+    fn rigid_flange_tips_about_its_edge() {
+        let layout = Layout::Rectangular {
+            rows: 2,
+            columns: 2,
+            pitch_x: 100.0,
+            pitch_y: 100.0,
+        };
+        let loads = PatternLoads {
+            my: -1e6,
+            ..Default::default()
+        };
+        let elastic = BoltPattern {
+            layout: layout.clone(),
+            distribution: Distribution::Elastic,
+        }
+        .distribute(&loads);
+        let rigid = BoltPattern {
+            layout,
+            distribution: Distribution::Rigid { edge: 70.0 },
+        }
+        .distribute(&loads);
+        // Negative M_y puts +x in tension, the edge pivot keeps every bolt in tension
+        assert!((elastic[1].axial - 1e6 / (4.0 * 50.0)).abs() < 1e-9);
+        assert!(rigid.iter().all(|b| b.axial > 0.0));
+        assert!(rigid[1].axial < elastic[1].axial);
+    }
+}
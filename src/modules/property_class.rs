@@ -0,0 +1,292 @@
+use crate::modules::{
+    analysis::Section,
+    elements::Nut,
+    material::{Material, MaterialType},
+    thread::Thread,
+};
+
+const MPA_PER_KSI: f64 = 6.894757;
+const MM_PER_INCH: f64 = 25.4;
+const PA_PER_MPA: f64 = 1e6;
+
+/// Groups of classes that can be mated with each other
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Family {
+    Iso,  // ISO 898-1 / 898-2 carbon and alloy steel
+    A2,   // ISO 3506 austenitic stainless A2
+    A4,   // ISO 3506 austenitic stainless A4
+    Sae,  // SAE J429 / J995
+    Astm, // ASTM A193 / A194
+}
+
+impl Family {
+    fn steel(self) -> Steel {
+        match self {
+            Family::A2 | Family::A4 => Steel::Austenitic,
+            _ => Steel::Ferritic,
+        }
+    }
+}
+
+/// Bulk properties shared by every class of a family
+enum Steel {
+    Ferritic,
+    Austenitic,
+}
+
+impl Steel {
+    /// (E MPa, ν, ρ kg/m³, α 1/K)
+    fn constants(&self) -> (f64, f64, f64, f64) {
+        match self {
+            Steel::Ferritic => (205_000.0, 0.3, 7850.0, 11.5e-6),
+            Steel::Austenitic => (193_000.0, 0.3, 7900.0, 16.0e-6),
+        }
+    }
+}
+
+/// Mechanical properties of a bolt property class or grade (MPa, %)
+#[derive(Debug, Clone, PartialEq)]
+pub struct PropertyClass {
+    pub designation: &'static str,
+    pub standard: &'static str,
+    pub family: Family,
+    pub rank: u32,                 // compared against the nut class when mating
+    pub max_diameter: f64,         // mm, the row applies up to and including this size
+    pub tensile_strength: f64,     // R_m min
+    pub yield_strength: f64,       // R_p0.2 or R_eL min
+    pub proof_stress: Option<f64>, // S_p
+    pub elongation: Option<f64>,   // A min after fracture
+}
+
+/// Nut property class (ISO 898-2, ISO 3506-2, SAE J995, ASTM A194)
+#[derive(Debug, Clone, PartialEq)]
+pub struct NutClass {
+    pub designation: &'static str,
+    pub standard: &'static str,
+    pub family: Family,
+    pub rank: u32,
+    pub proof_stress: f64,     // S_p, M10 to M16 regular nuts
+    pub tensile_strength: f64, // R_m of the nut material, used for stripping
+}
+
+#[allow(clippy::too_many_arguments)]
+const fn bolt(
+    designation: &'static str,
+    standard: &'static str,
+    family: Family,
+    rank: u32,
+    max_diameter: f64,
+    tensile_strength: f64,
+    yield_strength: f64,
+    proof_stress: Option<f64>,
+    elongation: Option<f64>,
+) -> PropertyClass {
+    PropertyClass {
+        designation,
+        standard,
+        family,
+        rank,
+        max_diameter,
+        tensile_strength,
+        yield_strength,
+        proof_stress,
+        elongation,
+    }
+}
+
+const fn ksi(value: f64) -> f64 {
+    value * MPA_PER_KSI
+}
+
+const ISO_898_1: &str = "ISO 898-1";
+const ISO_3506_1: &str = "ISO 3506-1";
+const SAE_J429: &str = "SAE J429";
+const ASTM_A193: &str = "ASTM A193";
+
+/// Property classes, size dependent classes have one row per diameter range
+#[rustfmt::skip]
+const BOLT_CLASSES: &[PropertyClass] = &[
+    bolt("4.6", ISO_898_1, Family::Iso, 4, 39.0, 400.0, 240.0, Some(225.0), Some(22.0)),
+    bolt("4.8", ISO_898_1, Family::Iso, 4, 39.0, 420.0, 340.0, Some(310.0), None),
+    bolt("5.6", ISO_898_1, Family::Iso, 5, 39.0, 500.0, 300.0, Some(280.0), Some(20.0)),
+    bolt("5.8", ISO_898_1, Family::Iso, 5, 39.0, 520.0, 420.0, Some(380.0), None),
+    bolt("6.8", ISO_898_1, Family::Iso, 6, 39.0, 600.0, 480.0, Some(440.0), None),
+    bolt("8.8", ISO_898_1, Family::Iso, 8, 16.0, 800.0, 640.0, Some(580.0), Some(12.0)),
+    bolt("8.8", ISO_898_1, Family::Iso, 8, 39.0, 830.0, 660.0, Some(600.0), Some(12.0)),
+    bolt("9.8", ISO_898_1, Family::Iso, 9, 16.0, 900.0, 720.0, Some(650.0), Some(10.0)),
+    bolt("10.9", ISO_898_1, Family::Iso, 10, 39.0, 1040.0, 940.0, Some(830.0), Some(9.0)),
+    bolt("12.9", ISO_898_1, Family::Iso, 12, 39.0, 1220.0, 1100.0, Some(970.0), Some(8.0)),
+    bolt("A2-50", ISO_3506_1, Family::A2, 50, 39.0, 500.0, 210.0, None, None),
+    bolt("A2-70", ISO_3506_1, Family::A2, 70, 24.0, 700.0, 450.0, None, None),
+    bolt("A2-80", ISO_3506_1, Family::A2, 80, 24.0, 800.0, 600.0, None, None),
+    bolt("A4-50", ISO_3506_1, Family::A4, 50, 39.0, 500.0, 210.0, None, None),
+    bolt("A4-70", ISO_3506_1, Family::A4, 70, 24.0, 700.0, 450.0, None, None),
+    bolt("A4-80", ISO_3506_1, Family::A4, 80, 24.0, 800.0, 600.0, None, None),
+    bolt("Grade 2", SAE_J429, Family::Sae, 2, 0.75 * MM_PER_INCH, ksi(74.0), ksi(57.0), Some(ksi(55.0)), Some(18.0)),
+    bolt("Grade 2", SAE_J429, Family::Sae, 2, 1.5 * MM_PER_INCH, ksi(60.0), ksi(36.0), Some(ksi(33.0)), Some(18.0)),
+    bolt("Grade 5", SAE_J429, Family::Sae, 5, 1.0 * MM_PER_INCH, ksi(120.0), ksi(92.0), Some(ksi(85.0)), Some(14.0)),
+    bolt("Grade 5", SAE_J429, Family::Sae, 5, 1.5 * MM_PER_INCH, ksi(105.0), ksi(81.0), Some(ksi(74.0)), Some(14.0)),
+    bolt("Grade 8", SAE_J429, Family::Sae, 8, 1.5 * MM_PER_INCH, ksi(150.0), ksi(130.0), Some(ksi(120.0)), Some(12.0)),
+    bolt("B7", ASTM_A193, Family::Astm, 7, 2.5 * MM_PER_INCH, ksi(125.0), ksi(105.0), None, Some(16.0)),
+    bolt("B7", ASTM_A193, Family::Astm, 7, 4.0 * MM_PER_INCH, ksi(115.0), ksi(95.0), None, Some(16.0)),
+    bolt("B8 Class 1", ASTM_A193, Family::Astm, 1, 4.0 * MM_PER_INCH, ksi(75.0), ksi(30.0), None, Some(30.0)),
+    bolt("B16", ASTM_A193, Family::Astm, 7, 2.5 * MM_PER_INCH, ksi(125.0), ksi(105.0), None, Some(18.0)),
+];
+
+#[rustfmt::skip]
+const NUT_CLASSES: &[NutClass] = &[
+    NutClass { designation: "5", standard: "ISO 898-2", family: Family::Iso, rank: 5, proof_stress: 610.0, tensile_strength: 500.0 },
+    NutClass { designation: "6", standard: "ISO 898-2", family: Family::Iso, rank: 6, proof_stress: 700.0, tensile_strength: 600.0 },
+    NutClass { designation: "8", standard: "ISO 898-2", family: Family::Iso, rank: 8, proof_stress: 880.0, tensile_strength: 800.0 },
+    NutClass { designation: "10", standard: "ISO 898-2", family: Family::Iso, rank: 10, proof_stress: 1040.0, tensile_strength: 1000.0 },
+    NutClass { designation: "12", standard: "ISO 898-2", family: Family::Iso, rank: 12, proof_stress: 1150.0, tensile_strength: 1200.0 },
+    NutClass { designation: "A2-70", standard: "ISO 3506-2", family: Family::A2, rank: 70, proof_stress: 700.0, tensile_strength: 700.0 },
+    NutClass { designation: "A2-80", standard: "ISO 3506-2", family: Family::A2, rank: 80, proof_stress: 800.0, tensile_strength: 800.0 },
+    NutClass { designation: "A4-70", standard: "ISO 3506-2", family: Family::A4, rank: 70, proof_stress: 700.0, tensile_strength: 700.0 },
+    NutClass { designation: "A4-80", standard: "ISO 3506-2", family: Family::A4, rank: 80, proof_stress: 800.0, tensile_strength: 800.0 },
+    NutClass { designation: "Grade 2", standard: "SAE J995", family: Family::Sae, rank: 2, proof_stress: ksi(90.0), tensile_strength: ksi(74.0) },
+    NutClass { designation: "Grade 5", standard: "SAE J995", family: Family::Sae, rank: 5, proof_stress: ksi(120.0), tensile_strength: ksi(120.0) },
+    NutClass { designation: "Grade 8", standard: "SAE J995", family: Family::Sae, rank: 8, proof_stress: ksi(150.0), tensile_strength: ksi(150.0) },
+    NutClass { designation: "2H", standard: "ASTM A194", family: Family::Astm, rank: 7, proof_stress: ksi(175.0), tensile_strength: ksi(125.0) },
+    NutClass { designation: "8", standard: "ASTM A194", family: Family::Astm, rank: 1, proof_stress: ksi(80.0), tensile_strength: ksi(75.0) },
+];
+
+/// All bolt property classes, one row per size range
+pub fn bolt_classes() -> &'static [PropertyClass] {
+    BOLT_CLASSES
+}
+
+/// Finds a bolt class by designation for a nominal diameter in mm, e.g. ("8.8", 20.0)
+pub fn bolt_class(designation: &str, diameter: f64) -> Option<&'static PropertyClass> {
+    let designation = designation.trim();
+    BOLT_CLASSES
+        .iter()
+        .find(|c| c.designation.eq_ignore_ascii_case(designation) && diameter <= c.max_diameter)
+}
+
+pub fn nut_class(designation: &str, family: Family) -> Option<&'static NutClass> {
+    let designation = designation.trim();
+    NUT_CLASSES
+        .iter()
+        .find(|c| c.family == family && c.designation.eq_ignore_ascii_case(designation))
+}
+
+/// Gives a bolt or stud material the properties of a class for its thread size and,
+/// unless the nut's own class already fits, the lowest nut class that does; None when
+/// the class does not cover the size
+pub fn assign(
+    designation: &str,
+    thread: &Thread,
+    material: &mut Material,
+    nut: Option<&mut Nut>,
+) -> Option<&'static PropertyClass> {
+    let class = bolt_class(designation, Section::from_thread(thread).d)?;
+    *material = class.to_material();
+
+    if let Some(nut) = nut {
+        let fits = nut
+            .material
+            .property_class
+            .as_deref()
+            .and_then(|n| nut_class(n, class.family))
+            .is_some_and(|n| n.fits(class));
+        if !fits && let Some(nut_class) = class.matching_nuts().first() {
+            nut.material = nut_class.to_material();
+        }
+    }
+    Some(class)
+}
+
+impl PropertyClass {
+    /// Nut classes that may be used with this bolt class, lowest first
+    pub fn matching_nuts(&self) -> Vec<&'static NutClass> {
+        NUT_CLASSES.iter().filter(|n| n.fits(self)).collect()
+    }
+
+    /// Builds a bolt material carrying this class' minimum properties
+    pub fn to_material(&self) -> Material {
+        let (e, nu, density, expansion) = self.family.steel().constants();
+        let mut material = Material::new(
+            format!("{} {}", self.standard, self.designation),
+            MaterialType::Metal,
+        );
+        material.set_standard(
+            self.standard,
+            self.standard.split(' ').next().unwrap_or(""),
+            None,
+        );
+        material.property_class = Some(self.designation.to_string());
+        material.density = Some(density);
+        material.youngs_modulus = Some(e * PA_PER_MPA);
+        material.shear_modulus = Some(e / (2.0 * (1.0 + nu)) * PA_PER_MPA);
+        material.poisson_ratio = Some(nu);
+        material.tensile_strength = Some(self.tensile_strength * PA_PER_MPA);
+        material.yield_strength = Some(self.yield_strength * PA_PER_MPA);
+        material.thermal_expansion = Some(expansion);
+        material
+    }
+}
+
+impl NutClass {
+    /// A nut may replace one of a lower class, ISO 898-2 clause 5
+    pub fn fits(&self, bolt: &PropertyClass) -> bool {
+        self.family == bolt.family && self.rank >= bolt.rank
+    }
+
+    pub fn to_material(&self) -> Material {
+        let (e, nu, density, expansion) = self.family.steel().constants();
+        let mut material = Material::new(
+            format!("{} {}", self.standard, self.designation),
+            MaterialType::Metal,
+        );
+        material.set_standard(
+            self.standard,
+            self.standard.split(' ').next().unwrap_or(""),
+            None,
+        );
+        material.property_class = Some(self.designation.to_string());
+        material.density = Some(density);
+        material.youngs_modulus = Some(e * PA_PER_MPA);
+        material.poisson_ratio = Some(nu);
+        material.tensile_strength = Some(self.tensile_strength * PA_PER_MPA);
+        material.thermal_expansion = Some(expansion);
+        material
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn size_dependent_classes() {
+        assert_eq!(bolt_class("8.8", 12.0).unwrap().yield_strength, 640.0);
+        assert_eq!(bolt_class("8.8", 20.0).unwrap().yield_strength, 660.0);
+        assert!(bolt_class("9.8", 20.0).is_none());
+        assert!(bolt_class("7.7", 12.0).is_none());
+    }
+
+    #[test]
+    fn nuts_match_bolts() {
+        let nuts = |class: &str, d: f64| -> Vec<&str> {
+            bolt_class(class, d)
+                .unwrap()
+                .matching_nuts()
+                .iter()
+                .map(|n| n.designation)
+                .collect()
+        };
+        assert_eq!(nuts("8.8", 12.0), vec!["8", "10", "12"]);
+        assert_eq!(nuts("12.9", 12.0), vec!["12"]);
+        assert_eq!(nuts("A2-70", 12.0), vec!["A2-70", "A2-80"]);
+        assert_eq!(nuts("B7", 24.0), vec!["2H"]);
+    }
+
+    #[test]
+    fn material_is_populated() {
+        let material = bolt_class("10.9", 12.0).unwrap().to_material();
+        assert_eq!(material.yield_strength, Some(940e6));
+        assert_eq!(material.property_class.as_deref(), Some("10.9"));
+        assert!(material.youngs_modulus.is_some());
+    }
+}
@@ -0,0 +1,500 @@
+use std::f64::consts::PI;
+
+use crate::modules::{
+    analysis::{AnalysisError, Section, youngs_modulus},
+    elements::Clamped,
+    joint::{Counterpart, Fastener, JointType, ResolvedJoint},
+};
+
+/// Substitute length factors from VDI 2230 5.1.1
+const HEAD_LENGTH: f64 = 0.5; // l_SK / d, hexagon head
+const SOCKET_HEAD_LENGTH: f64 = 0.4; // l_SK / d, internal drive and countersunk heads
+const ENGAGED_LENGTH: f64 = 0.5; // l_G / d
+const NUT_LENGTH: f64 = 0.4; // l_M / d, through bolted joint
+const TAPPED_LENGTH: f64 = 0.33; // l_M / d, tapped thread joint
+
+/// One cylindrical spring in the bolt's series model
+#[derive(Debug, Clone)]
+pub struct Spring {
+    pub name: &'static str,
+    pub symbol: &'static str,
+    pub length: f64,  // mm, substitute length for head, thread and nut
+    pub area: f64,    // mm²
+    pub modulus: f64, // MPa
+}
+
+impl Spring {
+    fn new(name: &'static str, symbol: &'static str, length: f64, area: f64, modulus: f64) -> Self {
+        Self {
+            name,
+            symbol,
+            length,
+            area,
+            modulus,
+        }
+    }
+
+    /// Elastic resilience δ = l / (E·A) in mm/N
+    pub fn resilience(&self) -> f64 {
+        self.length / (self.modulus * self.area)
+    }
+}
+
+/// Bolt resilience δS broken down into its segments
+#[derive(Debug, Clone)]
+pub struct BoltResilience {
+    pub segments: Vec<Spring>,
+}
+
+impl BoltResilience {
+    pub fn total(&self) -> f64 {
+        self.segments.iter().map(Spring::resilience).sum()
+    }
+
+    /// Fraction of the total resilience contributed by each segment
+    pub fn shares(&self) -> Vec<(&'static str, f64)> {
+        let total = self.total();
+        self.segments
+            .iter()
+            .map(|s| (s.name, s.resilience() / total))
+            .collect()
+    }
+}
+
+/// Engaged thread δ_G plus the nut or tapped hole δ_M at one end of the fastener
+fn engagement(
+    segments: &mut Vec<Spring>,
+    section: &Section,
+    e_s: f64,
+    counterpart: &Counterpart<'_>,
+) -> Result<(), AnalysisError> {
+    segments.push(Spring::new(
+        "Engaged thread",
+        "δ_G",
+        ENGAGED_LENGTH * section.d,
+        section.minor_area(),
+        e_s,
+    ));
+
+    let nut = match counterpart {
+        Counterpart::Nut(_) => Spring::new(
+            "Nut",
+            "δ_M",
+            NUT_LENGTH * section.d,
+            section.nominal_area(),
+            e_s,
+        ),
+        Counterpart::Threaded(threaded) => Spring::new(
+            "Tapped hole",
+            "δ_M",
+            TAPPED_LENGTH * section.d,
+            section.nominal_area(),
+            youngs_modulus(&threaded.material, "Tapped part")?,
+        ),
+    };
+    segments.push(nut);
+
+    Ok(())
+}
+
+/// Splits the fastener into head, shank, free thread and engaged thread springs
+/// (VDI 2230 5.1.1), the clamp length sets how much of the thread is loaded
+pub fn bolt_resilience(
+    joint: &ResolvedJoint<'_>,
+    clamp_length: f64,
+) -> Result<BoltResilience, AnalysisError> {
+    let mut segments = Vec::new();
+
+    match joint.fastener {
+        Fastener::Bolt(bolt) => {
+            let section = Section::from_thread(&bolt.thread);
+            let e_s = youngs_modulus(&bolt.material, "Bolt")?;
+            // A countersunk head's length includes the cone, half of which is clamped
+            let shank_length = ((bolt.length - bolt.thread_length) as f64 - bolt.sunk_depth())
+                .clamp(0.0, clamp_length);
+            let head_length = if bolt.head_type.internal_drive() {
+                SOCKET_HEAD_LENGTH
+            } else {
+                HEAD_LENGTH
+            };
+
+            segments.push(Spring::new(
+                "Head",
+                "δ_SK",
+                head_length * section.d,
+                section.nominal_area(),
+                e_s,
+            ));
+            if shank_length > 0.0 {
+                segments.push(Spring::new(
+                    "Shank",
+                    "δ_1",
+                    shank_length,
+                    section.nominal_area(),
+                    e_s,
+                ));
+            }
+            segments.push(Spring::new(
+                "Free loaded thread",
+                "δ_Gew",
+                clamp_length - shank_length,
+                section.minor_area(),
+                e_s,
+            ));
+            engagement(&mut segments, &section, e_s, &joint.counterpart)?;
+        }
+        Fastener::Stud(stud) => {
+            let section = Section::from_thread(&stud.thread_b);
+            let anchor = Section::from_thread(&stud.thread_a);
+            let e_s = youngs_modulus(&stud.material, "Stud")?;
+            let shank_diameter = stud.shank_diameter as f64;
+            let shank_length = (stud.shank_length as f64).min(clamp_length);
+
+            // Without a tapped part the anchored end is held by a nut of the stud's modulus
+            segments.push(match joint.anchor {
+                Some(threaded) => Spring::new(
+                    "Anchored tapped hole",
+                    "δ_MA",
+                    TAPPED_LENGTH * anchor.d,
+                    anchor.nominal_area(),
+                    youngs_modulus(&threaded.material, "Tapped part")?,
+                ),
+                None => Spring::new(
+                    "Anchoring nut",
+                    "δ_MA",
+                    NUT_LENGTH * anchor.d,
+                    anchor.nominal_area(),
+                    e_s,
+                ),
+            });
+            segments.push(Spring::new(
+                "Anchored thread",
+                "δ_GA",
+                ENGAGED_LENGTH * anchor.d,
+                anchor.minor_area(),
+                e_s,
+            ));
+            if shank_length > 0.0 {
+                segments.push(Spring::new(
+                    "Shank",
+                    "δ_1",
+                    shank_length,
+                    PI / 4.0 * shank_diameter.powi(2),
+                    e_s,
+                ));
+            }
+            segments.push(Spring::new(
+                "Free loaded thread",
+                "δ_Gew",
+                clamp_length - shank_length,
+                section.minor_area(),
+                e_s,
+            ));
+            engagement(&mut segments, &section, e_s, &joint.counterpart)?;
+        }
+    }
+
+    Ok(BoltResilience { segments })
+}
+
+/// Substitute deformation body of the clamped parts, all dimensions in mm
+#[derive(Debug, Clone)]
+pub struct DeformationCone {
+    pub joint_type: JointType,
+    pub bearing_diameter: f64, // d_W
+    pub outer_diameter: f64,   // D_A, f64::INFINITY for unbounded plates
+    pub limit_diameter: f64,   // D_A,Gr, largest diameter the cone reaches
+    pub clamp_length: f64,     // l_K
+    pub tan_phi: f64,          // cone half angle tangent
+}
+
+impl DeformationCone {
+    fn new(
+        joint_type: JointType,
+        bearing_diameter: f64,
+        outer_diameter: f64,
+        clamp_length: f64,
+    ) -> Self {
+        let (d_w, l_k) = (bearing_diameter, clamp_length);
+        let beta_l = l_k / d_w;
+        let y = outer_diameter.min(10.0 * d_w) / d_w; // y is capped to keep the fit in range
+        let (tan_phi, w) = match joint_type {
+            JointType::ThroughBolt => (0.362 + 0.032 * (beta_l / 2.0).ln() + 0.153 * y.ln(), 1.0),
+            JointType::TappedThread => (0.348 + 0.013 * beta_l.ln() + 0.193 * y.ln(), 2.0),
+        };
+
+        Self {
+            joint_type,
+            bearing_diameter,
+            outer_diameter,
+            limit_diameter: d_w + w * l_k * tan_phi,
+            clamp_length,
+            tan_phi,
+        }
+    }
+
+    /// Cone half angle φ in degrees
+    pub fn angle(&self) -> f64 {
+        self.tan_phi.atan().to_degrees()
+    }
+
+    /// Distance from a bearing face at which the cone meets the outer diameter
+    pub fn cone_height(&self) -> f64 {
+        let reach = match self.joint_type {
+            JointType::ThroughBolt => self.clamp_length / 2.0,
+            JointType::TappedThread => self.clamp_length,
+        };
+        let d_a = self.outer_diameter.min(self.limit_diameter);
+        ((d_a - self.bearing_diameter).max(0.0) / (2.0 * self.tan_phi)).min(reach)
+    }
+
+    /// Integral of 4 / (π·(D² - d_h²)) from a bearing face to depth u
+    fn integral(&self, u: f64, hole_diameter: f64) -> f64 {
+        let (d_w, d_h, t) = (self.bearing_diameter, hole_diameter, self.tan_phi);
+        let u_s = self.cone_height();
+        let cone = |u: f64| {
+            let d = d_w + 2.0 * u * t;
+            ((d - d_h) / (d + d_h)).ln() / (PI * t * d_h)
+        };
+
+        if u <= u_s {
+            cone(u) - cone(0.0)
+        } else {
+            let d_a = (d_w + 2.0 * u_s * t).min(self.outer_diameter);
+            cone(u_s) - cone(0.0) + 4.0 * (u - u_s) / (PI * (d_a * d_a - d_h * d_h))
+        }
+    }
+
+    /// Resilience of the slice between depths z0 and z1 for a unit modulus
+    fn slice(&self, z0: f64, z1: f64, hole_diameter: f64) -> f64 {
+        let g = |u: f64| self.integral(u, hole_diameter);
+        match self.joint_type {
+            JointType::TappedThread => g(z1) - g(z0),
+            JointType::ThroughBolt => {
+                let (l_k, mid) = (self.clamp_length, self.clamp_length / 2.0);
+                let head_side = if z0 < mid {
+                    g(z1.min(mid)) - g(z0)
+                } else {
+                    0.0
+                };
+                let nut_side = if z1 > mid {
+                    g(l_k - z0.max(mid)) - g(l_k - z1)
+                } else {
+                    0.0
+                };
+                head_side + nut_side
+            }
+        }
+    }
+}
+
+/// Resilience of a single clamped layer
+#[derive(Debug, Clone)]
+pub struct LayerResilience {
+    pub depth: f64,      // mm, head bearing face to top of layer
+    pub thickness: f64,  // mm
+    pub modulus: f64,    // MPa
+    pub resilience: f64, // mm/N
+}
+
+/// Plate resilience δP of the clamped stack
+#[derive(Debug, Clone)]
+pub struct PlateResilience {
+    pub layers: Vec<LayerResilience>,
+    pub cone: DeformationCone,
+}
+
+impl PlateResilience {
+    pub fn total(&self) -> f64 {
+        self.layers.iter().map(|l| l.resilience).sum()
+    }
+}
+
+/// Plate resilience using the deformation cone and sleeve substitution (VDI 2230 5.1.2),
+/// layers are listed from the head side and integrated with their own modulus and hole
+pub fn plate_resilience(
+    clamped: &[&Clamped],
+    bearing_diameter: f64,
+    joint_type: JointType,
+) -> Result<PlateResilience, AnalysisError> {
+    let clamp_length: f64 = clamped.iter().map(|c| c.thickness as f64).sum();
+    if clamp_length <= 0.0 {
+        return Err(AnalysisError::InvalidGeometry(
+            "clamp length must be positive",
+        ));
+    }
+    if clamped.iter().any(|c| c.id as f64 >= bearing_diameter) {
+        return Err(AnalysisError::InvalidGeometry(
+            "bearing diameter must exceed the hole diameter",
+        ));
+    }
+
+    // The narrowest layer limits the substitute body
+    let outer_diameter = clamped
+        .iter()
+        .filter_map(|c| c.od.map(|od| od as f64))
+        .fold(f64::INFINITY, f64::min);
+    let cone = DeformationCone::new(joint_type, bearing_diameter, outer_diameter, clamp_length);
+
+    let mut depth = 0.0;
+    let mut layers = Vec::with_capacity(clamped.len());
+    for part in clamped {
+        let thickness = part.thickness as f64;
+        let modulus = youngs_modulus(&part.material, "Clamped part")?;
+        let resilience = cone.slice(depth, depth + thickness, part.id as f64) / modulus;
+        layers.push(LayerResilience {
+            depth,
+            thickness,
+            modulus,
+            resilience,
+        });
+        depth += thickness;
+    }
+
+    Ok(PlateResilience { layers, cone })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::{
+        elements::{Nut, Stud, Threaded},
+        material::{Material, MaterialType},
+        thread::{Thread, ThreadHand},
+    };
+
+    fn stud(shank_diameter: f32) -> Stud {
+        let thread = Thread::new_metric(16.0, 2.0, None, ThreadHand::Right, None);
+        let mut material = Material::new("42CrMo4", MaterialType::Metal);
+        material.youngs_modulus = Some(205e9);
+        Stud {
+            thread_a: thread.clone(),
+            thread_length_a: 24.0,
+            thread_b: thread,
+            thread_length_b: 30.0,
+            shank_diameter,
+            shank_length: 50.0,
+            material,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn waisted_stud_is_softer() {
+        let nut = Nut::default();
+        let clamped = Clamped::default();
+        let full = stud(16.0);
+        let waisted = stud(11.5);
+
+        let resilience = |stud| {
+            let joint = ResolvedJoint {
+                fastener: Fastener::Stud(stud),
+                counterpart: Counterpart::Nut(&nut),
+                clamped: vec![&clamped],
+                anchor: None,
+                washers: [None, None],
+                eccentricity: None,
+            };
+            bolt_resilience(&joint, 60.0).unwrap()
+        };
+        let full = resilience(&full);
+        let waisted = resilience(&waisted);
+
+        assert_eq!(full.segments.len(), waisted.segments.len());
+        assert!(waisted.total() > full.total());
+
+        // Only the shank segment differs, by the ratio of the areas
+        let shank = |r: &BoltResilience| {
+            r.segments
+                .iter()
+                .find(|s| s.symbol == "δ_1")
+                .unwrap()
+                .resilience()
+        };
+        let ratio = shank(&waisted) / shank(&full);
+        assert!((ratio - (16.0f64 / 11.5).powi(2)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn unanchored_stud_is_held_like_a_nut() {
+        let nut = Nut::default();
+        let clamped = Clamped::default();
+        let stud = stud(16.0);
+        let mut tapped = Threaded::default();
+        tapped.material.youngs_modulus = Some(70e9);
+
+        let anchoring = |anchor| {
+            let joint = ResolvedJoint {
+                fastener: Fastener::Stud(&stud),
+                counterpart: Counterpart::Nut(&nut),
+                clamped: vec![&clamped],
+                anchor,
+                washers: [None, None],
+                eccentricity: None,
+            };
+            let resilience = bolt_resilience(&joint, 60.0).unwrap();
+            let spring = resilience.segments[0].clone();
+            assert_eq!(spring.symbol, "δ_MA");
+            spring
+        };
+        let unanchored = anchoring(None);
+        assert_eq!(unanchored.name, "Anchoring nut");
+        assert!((unanchored.length - NUT_LENGTH * 16.0).abs() < 1e-9);
+        assert_eq!(unanchored.modulus, 205e3);
+
+        let anchored = anchoring(Some(&tapped));
+        assert_eq!(anchored.name, "Anchored tapped hole");
+        assert!((anchored.length - TAPPED_LENGTH * 16.0).abs() < 1e-9);
+        assert_eq!(anchored.modulus, 70e3);
+    }
+
+    fn plate(thickness: f32, youngs_modulus: f64) -> Clamped {
+        let mut material = Material::new("Plate", MaterialType::Metal);
+        material.youngs_modulus = Some(youngs_modulus);
+        Clamped {
+            id: 13.5,
+            od: None,
+            thickness,
+            chamfer_top: None,
+            chamfer_bottom: None,
+            friction: None,
+            material,
+        }
+    }
+
+    #[test]
+    fn plate_cone_matches_closed_form() {
+        let (d_w, d_h, l_k, e_p) = (17.0, 13.5, 40.0, 205_000.0);
+        let clamped = plate(40.0, 205e9);
+        let result = plate_resilience(&[&clamped], d_w, JointType::ThroughBolt).unwrap();
+
+        // VDI 2230 (5.1/24), cone only with w = 1
+        let cone = &result.cone;
+        let d_end = d_w + l_k * cone.tan_phi;
+        let expected = 2.0 * ((d_w + d_h) * (d_end - d_h) / ((d_w - d_h) * (d_end + d_h))).ln()
+            / (e_p * PI * d_h * cone.tan_phi);
+        assert!((result.total() - expected).abs() / expected < 1e-9);
+    }
+
+    #[test]
+    fn split_stack_matches_single_plate() {
+        let single = plate(40.0, 205e9);
+        let (upper, lower) = (plate(15.0, 205e9), plate(25.0, 205e9));
+        for joint_type in [JointType::ThroughBolt, JointType::TappedThread] {
+            let single = plate_resilience(&[&single], 17.0, joint_type).unwrap();
+            let split = plate_resilience(&[&upper, &lower], 17.0, joint_type).unwrap();
+            assert_eq!(split.layers.len(), 2);
+            assert!((single.total() - split.total()).abs() / single.total() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn aluminium_layer_is_softer() {
+        let steel = plate(20.0, 205e9);
+        let aluminium = plate(20.0, 70e9);
+        let result = plate_resilience(&[&steel, &aluminium], 17.0, JointType::ThroughBolt).unwrap();
+        // Symmetric cones, so only the modulus separates the layers
+        let ratio = result.layers[1].resilience / result.layers[0].resilience;
+        assert!((ratio - 205.0 / 70.0).abs() < 1e-9);
+    }
+}
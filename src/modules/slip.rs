@@ -0,0 +1,283 @@
+use std::f64::consts::PI;
+
+use crate::modules::{
+    analysis::{AnalysisError, Section},
+    engagement::shear_ratio,
+    joint::{Fastener, JointType, ResolvedJoint},
+};
+
+const PA_PER_MPA: f64 = 1e6;
+
+/// Transverse load transmitted by friction between the clamped parts (VDI 2230 R2, R12)
+#[derive(Debug, Clone)]
+pub struct Slip {
+    pub friction: f64,            // μ_Tmin over the slipping interfaces
+    pub surfaces: f64,            // q_F = q_M, the interfaces slipping
+    pub clamp_load_required: f64, // F_KQerf (N)
+}
+
+impl Slip {
+    /// Safety against slipping S_G for a residual clamp load
+    pub fn safety(&self, residual_clamp_load: f64) -> f64 {
+        if self.clamp_load_required > 0.0 {
+            residual_clamp_load / self.clamp_load_required
+        } else {
+            f64::INFINITY
+        }
+    }
+}
+
+/// Interfaces between the clamped parts, each with the μ_T of the part above it
+///
+/// The last part of a tapped joint, or of a stud anchored in a base, also rubs on the
+/// tapped part. Parts without a friction coefficient use `default_friction`.
+fn interface_friction(joint: &ResolvedJoint<'_>, default_friction: f64) -> Vec<(f64, f64)> {
+    let interfaces = match joint.joint_type() {
+        JointType::ThroughBolt => joint.clamped.len().saturating_sub(1),
+        JointType::TappedThread => joint.clamped.len(),
+    };
+    let mut depth = joint.head_washer_thickness();
+    joint
+        .clamped
+        .iter()
+        .take(interfaces)
+        .map(|part| {
+            depth += part.thickness as f64;
+            let friction = part.friction.map_or(default_friction, f64::from);
+            (depth, friction)
+        })
+        .collect()
+}
+
+/// Clamp load needed to carry a transverse force and torque by friction
+///
+/// F_KQerf = F_Q/(q_F·μ_Tmin) + M_Y/(q_M·r_a·μ_Tmin), with μ_Tmin the lowest coefficient
+/// of the interfaces in the stack and q_F = q_M their number, the same shear planes
+/// `slip_bearing` counts. A stack without interfaces slips against parts outside it,
+/// on `default_surfaces` interfaces of `default_friction`.
+pub fn slip(
+    joint: &ResolvedJoint<'_>,
+    transverse: f64,
+    torsion: f64,
+    friction_radius: f64,
+    default_friction: f64,
+    default_surfaces: f64,
+) -> Slip {
+    let interfaces = interface_friction(joint, default_friction);
+    let (friction, surfaces) = match interfaces.iter().map(|&(_, f)| f).reduce(f64::min) {
+        Some(friction) => (friction, interfaces.len() as f64),
+        None => (default_friction, default_surfaces),
+    };
+    let clamp_load_required = if friction > 0.0 && surfaces > 0.0 {
+        let torsion = if friction_radius > 0.0 {
+            torsion / (surfaces * friction_radius)
+        } else {
+            0.0
+        };
+        (transverse / surfaces + torsion) / friction
+    } else {
+        0.0
+    };
+    Slip {
+        friction,
+        surfaces,
+        clamp_load_required,
+    }
+}
+
+/// Stresses when the joint is allowed to slip into bearing (MPa)
+#[derive(Debug, Clone)]
+pub struct SlipBearing {
+    pub shear_area: f64,     // A_τ at the shear planes (mm²)
+    pub shear_stress: f64,   // τ_Q in the bolt
+    pub shear_strength: f64, // τ_B = (τ_B/R_m)·R_m of the bolt
+    pub hole_stress: f64,    // σ_L = F_Q / (d·t) in the thinnest part
+    pub hole_limit: f64,     // R_m of that part
+}
+
+impl SlipBearing {
+    /// Safety against shearing off S_A
+    pub fn shear_safety(&self) -> f64 {
+        self.shear_strength / self.shear_stress
+    }
+}
+
+/// Shank shear and hole bearing of a joint that slips into bolt contact
+///
+/// Each interface is a shear plane carrying `transverse / planes`. A plane within the
+/// unthreaded shank is sheared on the nominal diameter, otherwise on the minor diameter.
+pub fn slip_bearing(
+    joint: &ResolvedJoint<'_>,
+    section: &Section,
+    transverse: f64,
+) -> Result<SlipBearing, AnalysisError> {
+    let (material, part, shank) = match joint.fastener {
+        Fastener::Bolt(bolt) => (
+            &bolt.material,
+            "Bolt",
+            // Head side: the plain shank runs from the head to the thread run-out
+            ((bolt.length - bolt.thread_length) as f64, section.d, true),
+        ),
+        Fastener::Stud(stud) => (
+            &stud.material,
+            "Stud",
+            // Nut side: the nut thread comes first, then the shank
+            (
+                stud.thread_length_b as f64,
+                stud.shank_diameter as f64,
+                false,
+            ),
+        ),
+    };
+    let (shank_edge, shank_diameter, shank_first) = shank;
+    let tensile = material.tensile_strength.map(|r| r / PA_PER_MPA).ok_or(
+        AnalysisError::MissingProperty {
+            part,
+            property: "tensile strength",
+        },
+    )?;
+
+    let planes = interface_friction(joint, 0.0);
+    let shear_area = planes
+        .iter()
+        .map(|&(depth, _)| {
+            let in_shank = if shank_first {
+                depth <= shank_edge
+            } else {
+                depth > shank_edge
+            };
+            if in_shank && shank_diameter > 0.0 {
+                PI / 4.0 * shank_diameter.powi(2)
+            } else {
+                section.minor_area()
+            }
+        })
+        .fold(f64::INFINITY, f64::min);
+    if !shear_area.is_finite() {
+        return Err(AnalysisError::InvalidGeometry(
+            "slipping needs an interface between clamped parts",
+        ));
+    }
+    let shear_stress = transverse / planes.len() as f64 / shear_area;
+
+    let (thinnest, hole_limit) = joint
+        .clamped
+        .iter()
+        .map(|c| {
+            let limit = c.material.tensile_strength.map(|r| r / PA_PER_MPA);
+            (c.thickness as f64, limit)
+        })
+        .min_by(|a, b| a.0.total_cmp(&b.0))
+        .ok_or(AnalysisError::InvalidGeometry("no clamped parts"))?;
+    let hole_limit = hole_limit.ok_or(AnalysisError::MissingProperty {
+        part: "Clamped part",
+        property: "tensile strength",
+    })?;
+
+    Ok(SlipBearing {
+        shear_area,
+        shear_stress,
+        shear_strength: shear_ratio(material) * tensile,
+        hole_stress: transverse / (section.d * thinnest),
+        hole_limit,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::{
+        elements::{Bolt, Clamped, Nut},
+        joint::Counterpart,
+        material::{Material, MaterialType},
+        thread::{Thread, ThreadHand},
+    };
+
+    fn plate(thickness: f32, friction: Option<f32>) -> Clamped {
+        let mut material = Material::new("Steel", MaterialType::Metal);
+        material.tensile_strength = Some(500e6);
+        Clamped {
+            id: 13.0,
+            thickness,
+            friction,
+            material,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn lowest_interface_friction_governs() {
+        let bolt = Bolt::default();
+        let nut = Nut::default();
+        let (top, middle, bottom) = (
+            plate(10.0, Some(0.3)),
+            plate(10.0, Some(0.1)),
+            plate(10.0, None),
+        );
+        let joint = ResolvedJoint {
+            fastener: Fastener::Bolt(&bolt),
+            counterpart: Counterpart::Nut(&nut),
+            clamped: vec![&top, &middle, &bottom],
+            anchor: None,
+            washers: [None, None],
+            eccentricity: None,
+        };
+        // The bottom part's friction faces the nut and is not an interface
+        let result = slip(&joint, 2_000.0, 0.0, 0.0, 0.15, 1.0);
+        assert!((result.friction - 0.1).abs() < 1e-6);
+        assert_eq!(result.surfaces, 2.0);
+        assert!((result.clamp_load_required - 10_000.0).abs() < 1e-3);
+        assert!((result.safety(12_000.0) - 1.2).abs() < 1e-6);
+
+        // A single part slips on the given interfaces outside the stack
+        let single = ResolvedJoint {
+            clamped: vec![&top],
+            ..joint
+        };
+        let result = slip(&single, 2_000.0, 0.0, 0.0, 0.15, 2.0);
+        assert_eq!((result.friction, result.surfaces), (0.15, 2.0));
+    }
+
+    #[test]
+    fn shear_plane_in_shank_or_thread() {
+        let mut material = Material::new("8.8", MaterialType::Metal);
+        material.tensile_strength = Some(800e6);
+        let thread = Thread::new_metric(12.0, 1.75, None, ThreadHand::Right, None);
+        let section = Section::from_thread(&thread);
+        let mut bolt = Bolt {
+            thread,
+            length: 50.0,
+            thread_length: 30.0,
+            material,
+            ..Default::default()
+        };
+        let nut = Nut::default();
+        let (top, bottom) = (plate(10.0, None), plate(20.0, None));
+
+        let in_shank = {
+            let joint = ResolvedJoint {
+                fastener: Fastener::Bolt(&bolt),
+                counterpart: Counterpart::Nut(&nut),
+                clamped: vec![&top, &bottom],
+                anchor: None,
+                washers: [None, None],
+                eccentricity: None,
+            };
+            slip_bearing(&joint, &section, 10_000.0).unwrap()
+        };
+        assert!((in_shank.shear_area - section.nominal_area()).abs() < 1e-9);
+        assert!((in_shank.hole_stress - 10_000.0 / 120.0).abs() < 1e-9);
+
+        bolt.thread_length = 45.0;
+        let joint = ResolvedJoint {
+            fastener: Fastener::Bolt(&bolt),
+            counterpart: Counterpart::Nut(&nut),
+            clamped: vec![&top, &bottom],
+            anchor: None,
+            washers: [None, None],
+            eccentricity: None,
+        };
+        let in_thread = slip_bearing(&joint, &section, 10_000.0).unwrap();
+        assert!(in_thread.shear_safety() < in_shank.shear_safety());
+    }
+}
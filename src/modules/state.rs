@@ -3,6 +3,38 @@ pub struct UIState {
     pub show_nav_panel: bool,
     pub show_prop_panel: bool,
     pub show_settings: bool,
+    pub thread_designation: String,
+    #[serde(default)]
+    pub bolt_designation: String,
+    #[serde(default)]
+    pub nut_designation: String,
+    #[serde(default)]
+    pub library_path: String, // last library file opened or saved, .json or .toml
+    #[serde(default)]
+    pub sweep: SweepEntries,
+    #[serde(skip)]
+    pub file_action: Option<FileAction>,
+    #[serde(skip)]
+    pub file_error: Option<String>,
+}
+
+/// Parameter sweep entries as typed, lists "M10, M12" or ranges "0.08..0.16 step 0.04"
+#[derive(serde::Deserialize, serde::Serialize, Default)]
+#[serde(default)]
+pub struct SweepEntries {
+    pub thread: String,
+    pub property_class: String,
+    pub friction: String,
+    pub clamp_thickness: String, // mm
+    pub temperature: String,     // °C
+}
+
+/// Library file window being shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileAction {
+    Open,
+    Save,
+    Merge,
 }
 
 impl UIState {
@@ -11,6 +43,18 @@ impl UIState {
             show_nav_panel: true,
             show_prop_panel: true,
             show_settings: false,
+            thread_designation: "M12".to_string(),
+            bolt_designation: "ISO 4762 M12x50".to_string(),
+            nut_designation: "ISO 4032 M12".to_string(),
+            library_path: "library.toml".to_string(),
+            sweep: SweepEntries {
+                thread: "M10, M12".to_string(),
+                property_class: "8.8, 10.9".to_string(),
+                friction: "0.08..0.16 step 0.04".to_string(),
+                ..Default::default()
+            },
+            file_action: None,
+            file_error: None,
         }
     }
 }
@@ -0,0 +1,705 @@
+use std::fmt;
+
+use crate::modules::{
+    analysis::Section,
+    calc::{CalcError, Case, Report},
+    elements::{Bolt, Clamped, Nut, Stud, Threaded, Washer},
+    fastener_catalogue::{self, FastenerError},
+    joint::BoltedJoint,
+    library::{Library, PartId},
+    property_class,
+    thread::Thread,
+    thread_catalogue, washer_catalogue,
+};
+
+/// Most combinations a sweep may expand to
+pub const MAX_POINTS: usize = 10_000;
+
+/// Results tabulated for each combination
+pub const HEADLINE: [&str; 4] = ["F_Mzul", "M_A", "σ_redB", "F_KRmin"];
+
+/// Values of a numeric parameter, listed or stepped from one end of a range to the other
+///
+/// ```toml
+/// friction = { from = 0.08, to = 0.16, step = 0.02 }
+/// temperature = [20.0, 120.0]
+/// ```
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum Values {
+    List(Vec<f64>),
+    Range { from: f64, to: f64, step: f64 },
+}
+
+impl Default for Values {
+    fn default() -> Self {
+        Self::List(Vec::new())
+    }
+}
+
+impl Values {
+    /// Every value, a range including both ends
+    pub fn expand(&self) -> Result<Vec<f64>, SweepError> {
+        match *self {
+            Self::List(ref values) => Ok(values.clone()),
+            Self::Range { from, to, step } => {
+                if step.is_nan() || step <= 0.0 || to < from {
+                    return Err(SweepError::Range(format!("{from}..{to} step {step}")));
+                }
+                let count = ((to - from) / step + 1e-9).floor() as usize + 1;
+                if count > MAX_POINTS {
+                    return Err(SweepError::TooMany(count));
+                }
+                // Rounded so the steps print as typed
+                Ok((0..count)
+                    .map(|i| ((from + i as f64 * step) * 1e9).round() / 1e9)
+                    .collect())
+            }
+        }
+    }
+
+    /// Reads "0.08, 0.12" as a list or "0.08..0.16 step 0.02" as a range; blank is empty
+    pub fn parse(text: &str) -> Result<Self, SweepError> {
+        let number = |text: &str| {
+            text.trim()
+                .parse::<f64>()
+                .map_err(|_| SweepError::Number(text.trim().to_string()))
+        };
+        if let Some((from, rest)) = text.split_once("..") {
+            let (to, step) = rest
+                .split_once("step")
+                .ok_or_else(|| SweepError::Range(text.trim().to_string()))?;
+            return Ok(Self::Range {
+                from: number(from)?,
+                to: number(to)?,
+                step: number(step)?,
+            });
+        }
+        list(text)
+            .iter()
+            .map(|value| number(value))
+            .collect::<Result<_, _>>()
+            .map(Self::List)
+    }
+}
+
+/// Comma separated entries, blanks dropped
+pub fn list(text: &str) -> Vec<String> {
+    text.split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Joint inputs varied by a sweep, an empty parameter keeping the joint's own value
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct Sweep {
+    pub thread: Vec<String>,         // catalogue designations, e.g. "M10"
+    pub property_class: Vec<String>, // bolt class, the nut follows when it no longer fits
+    pub friction: Values,            // μG min = μK min, the maxima keep the joint's scatter
+    pub clamp_thickness: Values,     // total of the clamped parts (mm), the fastener follows
+    pub temperature: Values,         // operating temperature of bolt and parts (°C)
+}
+
+impl Sweep {
+    /// Every combination, the last parameter varying fastest; a single unvaried
+    /// combination when nothing is swept
+    pub fn variants(&self) -> Result<Vec<Variant>, SweepError> {
+        fn axis<T>(values: Vec<T>) -> Vec<Option<T>> {
+            match values.is_empty() {
+                true => vec![None],
+                false => values.into_iter().map(Some).collect(),
+            }
+        }
+        let threads = axis(self.thread.clone());
+        let classes = axis(self.property_class.clone());
+        let friction = axis(self.friction.expand()?);
+        let thickness = axis(self.clamp_thickness.expand()?);
+        let temperature = axis(self.temperature.expand()?);
+
+        let count = [
+            threads.len(),
+            classes.len(),
+            friction.len(),
+            thickness.len(),
+            temperature.len(),
+        ]
+        .iter()
+        .fold(1usize, |count, len| count.saturating_mul(*len));
+        if count > MAX_POINTS {
+            return Err(SweepError::TooMany(count));
+        }
+
+        let mut variants = Vec::with_capacity(count);
+        for thread in &threads {
+            for class in &classes {
+                for mu in &friction {
+                    for h in &thickness {
+                        for t in &temperature {
+                            variants.push(Variant {
+                                thread: thread.clone(),
+                                property_class: class.clone(),
+                                friction: *mu,
+                                clamp_thickness: *h,
+                                temperature: *t,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+        Ok(variants)
+    }
+}
+
+#[derive(Debug)]
+pub enum SweepError {
+    Number(String), // list or range entry that is not a number
+    Range(String),  // range running backwards or without a positive step
+    TooMany(usize), // combinations past MAX_POINTS
+    Size(FastenerError),
+    Class { class: String, thread: String }, // class not given for the size
+    NoClampedParts,
+    Calc(CalcError),
+}
+
+impl fmt::Display for SweepError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Number(text) => write!(f, "\"{text}\" is not a number"),
+            Self::Range(text) => write!(f, "\"{text}\" is not a range from..to step s"),
+            Self::TooMany(count) => {
+                write!(
+                    f,
+                    "{count} combinations, more than the {MAX_POINTS} allowed"
+                )
+            }
+            Self::Size(error) => error.fmt(f),
+            Self::Class { class, thread } => write!(f, "no {class} property class for {thread}"),
+            Self::NoClampedParts => write!(f, "the joint has no clamped thickness to scale"),
+            Self::Calc(error) => error.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for SweepError {}
+
+/// One combination of swept values, None where the joint's own value is kept
+#[derive(serde::Serialize, Debug, Clone, Default, PartialEq)]
+pub struct Variant {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thread: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub property_class: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub friction: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub clamp_thickness: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f64>,
+}
+
+impl Variant {
+    /// Swept values with their column headings
+    pub fn labels(&self) -> Vec<(&'static str, String)> {
+        let mut labels = Vec::new();
+        if let Some(thread) = &self.thread {
+            labels.push(("Thread", thread.clone()));
+        }
+        if let Some(class) = &self.property_class {
+            labels.push(("Class", class.clone()));
+        }
+        if let Some(mu) = self.friction {
+            labels.push(("μ", mu.to_string()));
+        }
+        if let Some(h) = self.clamp_thickness {
+            labels.push(("Clamp mm", h.to_string()));
+        }
+        if let Some(t) = self.temperature {
+            labels.push(("T °C", t.to_string()));
+        }
+        labels
+    }
+
+    /// The case with this combination's values in place of the joint's own
+    ///
+    /// A new size keeps the bolt's property class unless the class is swept too.
+    pub fn apply(&self, case: &Case) -> Result<Case, SweepError> {
+        let mut case = case.clone();
+        let joint = case.joint.clone();
+        let library = &mut case.library;
+        if let Some(designation) = &self.thread {
+            let thread = thread_catalogue::parse(designation)
+                .map_err(|e| SweepError::Size(FastenerError::Thread(e)))?;
+            resize(library, &joint, thread)?;
+        }
+        let kept_class = || {
+            let material = match (joint.bolt_id, joint.stud_id) {
+                (Some(id), _) => &library.bolt(id)?.material,
+                (None, Some(id)) => &library.stud(id)?.material,
+                (None, None) => return None,
+            };
+            material
+                .property_class
+                .clone()
+                .filter(|_| self.thread.is_some())
+        };
+        if let Some(class) = self.property_class.clone().or_else(kept_class) {
+            assign_class(library, &joint, &class)?;
+        }
+        if let Some(thickness) = self.clamp_thickness {
+            clamp(library, &joint, thickness)?;
+        }
+        if let Some(mu) = self.friction {
+            let tightening = &mut case.tightening;
+            tightening.mu_thread_max += mu - tightening.mu_thread_min;
+            tightening.mu_thread_min = mu;
+            tightening.mu_head_max += mu - tightening.mu_head_min;
+            tightening.mu_head_min = mu;
+        }
+        if let Some(t) = self.temperature {
+            let temperatures = case.load.temperature.unwrap_or_default();
+            case.load.temperature = Some(crate::modules::thermal::Temperatures {
+                bolt: t,
+                clamped: t,
+                ..temperatures
+            });
+        }
+        Ok(case)
+    }
+}
+
+/// Each key once, for edits that must not be applied twice to a part used twice
+fn distinct(ids: impl IntoIterator<Item = PartId>) -> Vec<PartId> {
+    let mut ids: Vec<PartId> = ids.into_iter().collect();
+    ids.sort();
+    ids.dedup();
+    ids
+}
+
+/// Moves every threaded part of the joint to another size
+///
+/// Bolt and nut take the catalogue dimensions of their standard. Holes scale with the
+/// diameter so they keep their fit, washers are taken from the catalogue when it has them.
+fn resize(library: &mut Library, joint: &BoltedJoint, thread: Thread) -> Result<(), SweepError> {
+    let old = match (joint.bolt_id, joint.stud_id) {
+        (Some(id), _) => library.bolt(id).map(|bolt| &bolt.thread),
+        (None, Some(id)) => library.stud(id).map(|stud| &stud.thread_b),
+        (None, None) => None,
+    };
+    // The joint fails to resolve later on with the reason
+    let Some(old) = old else {
+        return Ok(());
+    };
+    let diameter = Section::from_thread(&thread).d;
+    let ratio = (diameter / Section::from_thread(old).d) as f32;
+
+    if let Some(bolt) = joint.bolt_id.and_then(|id| library.part_mut::<Bolt>(id)) {
+        *bolt = fastener_catalogue::resize_bolt(bolt, thread.clone()).map_err(SweepError::Size)?;
+    }
+    if let Some(stud) = joint.stud_id.and_then(|id| library.part_mut::<Stud>(id)) {
+        stud.thread_a = thread.clone();
+        stud.thread_b = thread.clone();
+        stud.shank_diameter *= ratio;
+    }
+    if let Some(nut) = joint.nut_id.and_then(|id| library.part_mut::<Nut>(id)) {
+        *nut = fastener_catalogue::resize_nut(nut, thread.clone()).map_err(SweepError::Size)?;
+    }
+    if let Some(tapped) = joint
+        .threaded_id
+        .and_then(|id| library.part_mut::<Threaded>(id))
+    {
+        tapped.thread = thread;
+    }
+    for id in distinct(joint.clamped_ids.iter().copied()) {
+        if let Some(part) = library.part_mut::<Clamped>(id) {
+            part.id *= ratio;
+            part.chamfer_top = part.chamfer_top.map(|c| c * ratio);
+            part.chamfer_bottom = part.chamfer_bottom.map(|c| c * ratio);
+        }
+    }
+    for id in distinct(joint.head_washer_id.into_iter().chain(joint.nut_washer_id)) {
+        if let Some(washer) = library.part_mut::<Washer>(id) {
+            match washer_catalogue::standard(washer.kind, diameter) {
+                Some(standard) => {
+                    *washer = Washer {
+                        material: washer.material.clone(),
+                        ..standard
+                    }
+                }
+                None => {
+                    washer.id *= ratio;
+                    washer.od *= ratio;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Gives the fastener a property class for its size, re-pairing the nut when needed
+pub fn assign_class(
+    library: &mut Library,
+    joint: &BoltedJoint,
+    class: &str,
+) -> Result<(), SweepError> {
+    let mut nut = joint.nut_id.and_then(|id| library.nut(id)).cloned();
+    let assigned = if let Some(bolt) = joint.bolt_id.and_then(|id| library.part_mut::<Bolt>(id)) {
+        property_class::assign(class, &bolt.thread, &mut bolt.material, nut.as_mut())
+            .ok_or_else(|| thread_catalogue::designation(&bolt.thread))
+    } else if let Some(stud) = joint.stud_id.and_then(|id| library.part_mut::<Stud>(id)) {
+        property_class::assign(class, &stud.thread_b, &mut stud.material, nut.as_mut())
+            .ok_or_else(|| thread_catalogue::designation(&stud.thread_b))
+    } else {
+        return Ok(());
+    };
+    if let Err(thread) = assigned {
+        return Err(SweepError::Class {
+            class: class.to_string(),
+            thread,
+        });
+    }
+    if let (Some(id), Some(fitted)) = (joint.nut_id, nut)
+        && let Some(nut) = library.part_mut::<Nut>(id)
+    {
+        *nut = fitted;
+    }
+    Ok(())
+}
+
+/// Scales the clamped parts to a total thickness and lengthens the fastener by the
+/// difference, so the thread engagement is unchanged
+fn clamp(library: &mut Library, joint: &BoltedJoint, thickness: f64) -> Result<(), SweepError> {
+    let total: f64 = joint
+        .clamped_ids
+        .iter()
+        .filter_map(|id| library.clamped(*id))
+        .map(|part| part.thickness as f64)
+        .sum();
+    if total <= 0.0 {
+        return Err(SweepError::NoClampedParts);
+    }
+    for id in distinct(joint.clamped_ids.iter().copied()) {
+        if let Some(part) = library.part_mut::<Clamped>(id) {
+            part.thickness = (part.thickness as f64 * thickness / total) as f32;
+        }
+    }
+
+    let extra = (thickness - total) as f32;
+    if let Some(bolt) = joint.bolt_id.and_then(|id| library.part_mut::<Bolt>(id)) {
+        let fully_threaded = bolt.thread_length >= bolt.length;
+        bolt.length += extra;
+        if fully_threaded {
+            bolt.thread_length = bolt.length;
+        }
+    }
+    if let Some(stud) = joint.stud_id.and_then(|id| library.part_mut::<Stud>(id)) {
+        stud.shank_length += extra;
+    }
+    Ok(())
+}
+
+/// One combination and its results, or why it could not be analysed
+#[derive(serde::Serialize, Debug, Clone)]
+pub struct Point {
+    pub variant: Variant,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub report: Option<Report>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl Point {
+    pub fn passed(&self) -> bool {
+        self.report.as_ref().is_some_and(|report| report.passed)
+    }
+
+    /// Whether every check with the id passed, such as R10 on each bearing face; None
+    /// when the point has no such check
+    pub fn check(&self, id: &str) -> Option<bool> {
+        let report = self.report.as_ref()?;
+        let mut checks = report.checks.iter().filter(|c| c.id == id).peekable();
+        checks.peek()?;
+        Some(checks.all(|c| c.passed))
+    }
+}
+
+/// Results of every combination of a sweep
+#[derive(serde::Serialize, Debug, Clone, Default)]
+pub struct SweepReport {
+    pub points: Vec<Point>,
+}
+
+/// Runs the full calculation for every combination of the case's sweep
+///
+/// A combination that cannot be built or analysed is reported with its error rather
+/// than ending the sweep.
+pub fn run(case: &Case) -> Result<SweepReport, SweepError> {
+    // Every combination edits its own copy, so copy only the parts the joint uses
+    let case = Case {
+        library: case.library.only(case.joint.references()),
+        joint: case.joint.clone(),
+        tightening: case.tightening.clone(),
+        load: case.load.clone(),
+        pattern: case.pattern.clone(),
+        pattern_loads: case.pattern_loads,
+        sweep: case.sweep.clone(),
+    };
+    let points = case
+        .sweep
+        .variants()?
+        .into_iter()
+        .map(|variant| {
+            let analysed = variant
+                .apply(&case)
+                .and_then(|case| case.analyse().map_err(SweepError::Calc));
+            match analysed {
+                Ok(analysis) => Point {
+                    variant,
+                    report: Some(Report::new(&analysis)),
+                    error: None,
+                },
+                Err(error) => Point {
+                    variant,
+                    report: None,
+                    error: Some(error.to_string()),
+                },
+            }
+        })
+        .collect();
+    Ok(SweepReport { points })
+}
+
+impl SweepReport {
+    pub fn passed(&self) -> usize {
+        self.points.iter().filter(|p| p.passed()).count()
+    }
+
+    /// Headings of the swept parameters, the same for every point
+    pub fn parameters(&self) -> Vec<&'static str> {
+        self.points.first().map_or_else(Vec::new, |point| {
+            point.variant.labels().iter().map(|(h, _)| *h).collect()
+        })
+    }
+
+    /// Check ids in the order the calculation reports them
+    pub fn check_ids(&self) -> Vec<&'static str> {
+        let mut ids = Vec::new();
+        for report in self.points.iter().filter_map(|p| p.report.as_ref()) {
+            for check in &report.checks {
+                if !ids.contains(&check.id) {
+                    ids.push(check.id);
+                }
+            }
+        }
+        ids
+    }
+
+    /// Swept values, headline results and the verdict of each point
+    pub fn table(&self) -> String {
+        let mut out = String::new();
+        for heading in self.parameters() {
+            out += &format!("{heading:<10} ");
+        }
+        for symbol in HEADLINE {
+            out += &format!("{symbol:>12} ");
+        }
+        out += "\n";
+        for point in &self.points {
+            for (_, value) in point.variant.labels() {
+                out += &format!("{value:<10} ");
+            }
+            match (&point.report, &point.error) {
+                (Some(report), _) => {
+                    for symbol in HEADLINE {
+                        let value = report.value(symbol).unwrap_or(f64::NAN);
+                        out += &format!("{value:>12.1} ");
+                    }
+                    out += if report.passed { "pass" } else { "FAIL" };
+                }
+                (None, error) => out += error.as_deref().unwrap_or_default(),
+            }
+            out += "\n";
+        }
+        out
+    }
+
+    /// Pass or fail of every check at every point, "-" where it was not made
+    pub fn matrix(&self) -> String {
+        let ids = self.check_ids();
+        let mut out = String::new();
+        for heading in self.parameters() {
+            out += &format!("{heading:<10} ");
+        }
+        for id in &ids {
+            out += &format!("{id:>5}");
+        }
+        out += "\n";
+        for point in &self.points {
+            for (_, value) in point.variant.labels() {
+                out += &format!("{value:<10} ");
+            }
+            for id in &ids {
+                let cell = match point.check(id) {
+                    Some(true) => "pass",
+                    Some(false) => "FAIL",
+                    None => "-",
+                };
+                out += &format!("{cell:>5}");
+            }
+            out += "\n";
+        }
+        out
+    }
+
+    pub fn json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    /// One record per point: swept values, headline results, verdict and each check
+    pub fn csv(&self) -> String {
+        let ids = self.check_ids();
+        let mut header: Vec<String> = self.parameters().iter().map(|h| h.to_string()).collect();
+        header.extend(HEADLINE.iter().map(|s| s.to_string()));
+        header.push("passed".to_string());
+        header.extend(ids.iter().map(|id| id.to_string()));
+        header.push("error".to_string());
+
+        let mut out = header.join(",") + "\n";
+        for point in &self.points {
+            let mut record: Vec<String> =
+                point.variant.labels().into_iter().map(|(_, v)| v).collect();
+            for symbol in HEADLINE {
+                let value = point.report.as_ref().and_then(|r| r.value(symbol));
+                record.push(value.map(|v| v.to_string()).unwrap_or_default());
+            }
+            record.push(point.passed().to_string());
+            for id in &ids {
+                record.push(point.check(id).map(|p| p.to_string()).unwrap_or_default());
+            }
+            record.push(format!(
+                "\"{}\"",
+                point.error.as_deref().unwrap_or_default()
+            ));
+            out += &(record.join(",") + "\n");
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::{
+        calc::CheckRow,
+        material::{Material, MaterialType},
+    };
+
+    #[test]
+    fn ranges_and_lists_expand() {
+        let sweep = Sweep {
+            thread: list("M10, M12"),
+            friction: Values::parse("0.08..0.16 step 0.04").unwrap(),
+            temperature: Values::parse("20, 120").unwrap(),
+            ..Default::default()
+        };
+        assert_eq!(sweep.friction.expand().unwrap(), vec![0.08, 0.12, 0.16]);
+        let variants = sweep.variants().unwrap();
+        assert_eq!(variants.len(), 12);
+        assert_eq!(variants[1].thread.as_deref(), Some("M10"));
+        assert_eq!(variants[1].temperature, Some(120.0));
+        assert_eq!(variants[1].property_class, None);
+
+        assert_eq!(
+            Sweep::default().variants().unwrap(),
+            vec![Variant::default()]
+        );
+        assert!(matches!(
+            Values::parse("0.16..0.08 step 0.02").unwrap().expand(),
+            Err(SweepError::Range(_))
+        ));
+        assert!(matches!(
+            Values::parse("0..1 step 1e-6").unwrap().expand(),
+            Err(SweepError::TooMany(_))
+        ));
+    }
+
+    #[test]
+    fn a_check_fails_when_any_face_fails() {
+        let face = |description, value| CheckRow {
+            id: "R10",
+            description,
+            value,
+            limit: 700.0,
+            passed: value <= 700.0,
+            warning: None,
+        };
+        let point = Point {
+            variant: Variant::default(),
+            report: Some(Report {
+                passed: false,
+                results: Vec::new(),
+                checks: vec![
+                    face("Surface pressure under head p_M ≤ p_G at assembly", 500.0),
+                    face("Surface pressure under nut p_M ≤ p_G at assembly", 900.0),
+                ],
+            }),
+            error: None,
+        };
+        assert_eq!(point.check("R10"), Some(false));
+        assert_eq!(point.check("R11"), None);
+    }
+
+    #[test]
+    fn sizes_and_classes_of_a_catalogue_joint() {
+        let mut library = Library::default();
+        let mut bolt = fastener_catalogue::bolt("ISO 4014 M12x60").unwrap();
+        let mut nut = fastener_catalogue::nut("ISO 4032 M12").unwrap();
+        property_class::assign("8.8", &bolt.thread, &mut bolt.material, Some(&mut nut));
+        let mut plate = Material::new("Steel", MaterialType::Metal);
+        plate.youngs_modulus = Some(205e9);
+        plate.yield_strength = Some(490e6);
+        plate.tensile_strength = Some(700e6);
+        let joint = BoltedJoint {
+            bolt_id: Some(library.insert(String::new(), bolt)),
+            nut_id: Some(library.insert(String::new(), nut)),
+            clamped_ids: vec![library.insert(
+                String::new(),
+                Clamped {
+                    id: 13.5,
+                    thickness: 40.0,
+                    material: plate,
+                    ..Default::default()
+                },
+            )],
+            ..Default::default()
+        };
+        let case = Case {
+            library,
+            joint,
+            tightening: Default::default(),
+            load: Default::default(),
+            pattern: None,
+            pattern_loads: Default::default(),
+            sweep: Sweep {
+                thread: list("M10, M12, M100"),
+                property_class: list("8.8, 10.9"),
+                clamp_thickness: Values::List(vec![30.0, 40.0]),
+                ..Default::default()
+            },
+        };
+
+        let report = run(&case).unwrap();
+        assert_eq!(report.points.len(), 12);
+        assert_eq!(report.parameters(), vec!["Thread", "Class", "Clamp mm"]);
+        let preload = |i: usize| report.points[i].report.as_ref().unwrap().value("F_Mzul");
+        // M10 8.8, M12 8.8 and M12 10.9 at 40 mm
+        assert!(preload(1) < preload(5) && preload(5) < preload(7));
+        let clamp_length = |i: usize| report.points[i].report.as_ref().unwrap().value("l_K");
+        assert!((clamp_length(4).unwrap() - 30.0).abs() < 1e-3);
+        // No M100 bolt in the catalogue, reported without ending the sweep
+        assert!(report.points[8..].iter().all(|p| p.error.is_some()));
+    }
+}
@@ -0,0 +1,241 @@
+use crate::modules::{
+    analysis::{AnalysisError, property_at, youngs_modulus},
+    joint::{Fastener, ResolvedJoint},
+    material::{Material, MaterialType, Property, REFERENCE_TEMPERATURE},
+    resilience::{BoltResilience, PlateResilience},
+    utils::State,
+};
+
+const PA_PER_MPA: f64 = 1e6;
+
+/// Reference temperature the joint is assembled and its moduli are quoted at (°C)
+pub const ASSEMBLY_TEMPERATURE: f64 = REFERENCE_TEMPERATURE;
+
+/// Operating temperatures of the bolt and the clamped parts (°C)
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, Copy, PartialEq)]
+pub struct Temperatures {
+    pub assembly: f64,
+    pub bolt: f64,
+    pub clamped: f64,
+}
+
+impl Default for Temperatures {
+    fn default() -> Self {
+        Self::uniform(ASSEMBLY_TEMPERATURE)
+    }
+}
+
+impl Temperatures {
+    /// Bolt and clamped parts at the same operating temperature
+    pub fn uniform(temperature: f64) -> Self {
+        Self {
+            assembly: ASSEMBLY_TEMPERATURE,
+            bolt: temperature,
+            clamped: temperature,
+        }
+    }
+
+    /// Applies the app's thermal settings, keeping `current` when the temperatures are not synced
+    pub fn from_state(state: &State, current: Option<Self>) -> Option<Self> {
+        if !state.thermal {
+            return None;
+        }
+        if state.sync_temp {
+            return Some(Self::uniform(state.synced_temp));
+        }
+        Some(current.unwrap_or_else(|| Self::uniform(state.synced_temp)))
+    }
+}
+
+/// Preload change between assembly and operating temperature (VDI 2230 R4)
+#[derive(Debug, Clone)]
+pub struct ThermalChange {
+    pub temperatures: Temperatures,
+    pub bolt_expansion: f64,   // mm, free thermal growth of the bolt over l_K
+    pub plate_expansion: f64,  // mm, free thermal growth of the clamped stack
+    pub bolt_resilience: f64,  // δS at operating temperature (mm/N)
+    pub plate_resilience: f64, // δP at operating temperature (mm/N)
+    pub preload: f64,          // F_V at operating temperature
+    pub loss: f64,             // ΔF_Vth, positive when preload is lost
+}
+
+/// Linear change of Young's modulus per kelvin, used when the material has no modulus curve
+fn modulus_coefficient(material: &Material) -> f64 {
+    match material.material_type {
+        MaterialType::Metal => -2.6e-4,
+        MaterialType::Ceramic => -1.0e-4,
+        MaterialType::Composite => -1.0e-3,
+        MaterialType::Wood => -3.0e-3,
+        MaterialType::Polymer => -4.0e-3,
+        MaterialType::Other => 0.0,
+    }
+}
+
+/// Young's modulus at a temperature (MPa)
+pub fn youngs_modulus_at(
+    material: &Material,
+    part: &'static str,
+    temperature: f64,
+) -> Result<f64, AnalysisError> {
+    if material.has_curve(Property::YoungsModulus) {
+        return Ok(property_at(material, part, Property::YoungsModulus, temperature)? / PA_PER_MPA);
+    }
+    let modulus = youngs_modulus(material, part)?;
+    let ratio = 1.0 + modulus_coefficient(material) * (temperature - ASSEMBLY_TEMPERATURE);
+    if ratio <= 0.0 {
+        return Err(AnalysisError::InvalidGeometry(
+            "temperature is outside the modulus model",
+        ));
+    }
+    Ok(modulus * ratio)
+}
+
+/// Mean coefficient of thermal expansion α between assembly and `temperature` (1/K)
+fn thermal_expansion(
+    material: &Material,
+    part: &'static str,
+    temperature: f64,
+) -> Result<f64, AnalysisError> {
+    property_at(material, part, Property::ThermalExpansion, temperature)
+}
+
+/// Preload at operating temperature from the mismatch in thermal growth and stiffness
+///
+/// F_V·(δS + δP) − (α_S·l_K·ΔT_S − Σα_P·l_P·ΔT_P) = F_V,th·(δS,th + δP,th)
+pub fn thermal_change(
+    joint: &ResolvedJoint<'_>,
+    bolt: &BoltResilience,
+    plates: &PlateResilience,
+    temperatures: Temperatures,
+    preload: f64,
+) -> Result<ThermalChange, AnalysisError> {
+    let (material, part) = match joint.fastener {
+        Fastener::Bolt(bolt) => (&bolt.material, "Bolt"),
+        Fastener::Stud(stud) => (&stud.material, "Stud"),
+    };
+    let delta_bolt = temperatures.bolt - temperatures.assembly;
+    let delta_plate = temperatures.clamped - temperatures.assembly;
+
+    let stack = joint.stack();
+    let clamp_length: f64 = stack.iter().map(|c| c.thickness as f64).sum();
+    let bolt_expansion =
+        thermal_expansion(material, part, temperatures.bolt)? * clamp_length * delta_bolt;
+    let bolt_ratio =
+        youngs_modulus(material, part)? / youngs_modulus_at(material, part, temperatures.bolt)?;
+    let bolt_resilience = bolt.total() * bolt_ratio;
+
+    let mut plate_expansion = 0.0;
+    let mut plate_resilience = 0.0;
+    for (clamped, layer) in stack.iter().zip(&plates.layers) {
+        plate_expansion +=
+            thermal_expansion(&clamped.material, "Clamped part", temperatures.clamped)?
+                * layer.thickness
+                * delta_plate;
+        let hot = youngs_modulus_at(&clamped.material, "Clamped part", temperatures.clamped)?;
+        plate_resilience += layer.resilience * layer.modulus / hot;
+    }
+
+    let resilience = plates.total() + bolt.total();
+    let hot_preload = ((preload * resilience - (bolt_expansion - plate_expansion))
+        / (bolt_resilience + plate_resilience))
+        .max(0.0);
+
+    Ok(ThermalChange {
+        temperatures,
+        bolt_expansion,
+        plate_expansion,
+        bolt_resilience,
+        plate_resilience,
+        preload: hot_preload,
+        loss: preload - hot_preload,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::{
+        elements::{Bolt, Clamped, Nut},
+        joint::Counterpart,
+        material::{Curve, OutOfRange},
+        resilience::{bolt_resilience, plate_resilience},
+        thread::{Thread, ThreadHand},
+    };
+
+    fn metal(modulus: f64, expansion: f64) -> Material {
+        let mut material = Material::new("Metal", MaterialType::Metal);
+        material.youngs_modulus = Some(modulus);
+        material.thermal_expansion = Some(expansion);
+        material
+    }
+
+    fn change(plate: Material, temperatures: Temperatures) -> ThermalChange {
+        let thread = Thread::new_metric(10.0, 1.5, None, ThreadHand::Right, None);
+        let bolt = Bolt {
+            thread: thread.clone(),
+            length: 50.0,
+            thread_length: 26.0,
+            bearing_od: 15.0,
+            material: metal(205e9, 11.5e-6),
+            ..Default::default()
+        };
+        let nut = Nut {
+            thread,
+            thickness: 8.4,
+            material: metal(205e9, 11.5e-6),
+            ..Default::default()
+        };
+        let clamped = Clamped {
+            id: 11.0,
+            od: None,
+            thickness: 30.0,
+            chamfer_top: None,
+            chamfer_bottom: None,
+            friction: None,
+            material: plate,
+        };
+        let joint = ResolvedJoint {
+            fastener: Fastener::Bolt(&bolt),
+            counterpart: Counterpart::Nut(&nut),
+            clamped: vec![&clamped],
+            anchor: None,
+            washers: [None, None],
+            eccentricity: None,
+        };
+        let bolts = bolt_resilience(&joint, 30.0).unwrap();
+        let plates = plate_resilience(&joint.clamped, 15.0, joint.joint_type()).unwrap();
+        thermal_change(&joint, &bolts, &plates, temperatures, 20_000.0).unwrap()
+    }
+
+    #[test]
+    fn aluminium_housing_loses_preload_when_cold() {
+        let cold = change(metal(70e9, 23e-6), Temperatures::uniform(-40.0));
+        assert!(cold.loss > 0.0);
+        assert!(cold.plate_expansion < cold.bolt_expansion);
+
+        let hot = change(metal(70e9, 23e-6), Temperatures::uniform(120.0));
+        assert!(hot.loss < 0.0);
+    }
+
+    #[test]
+    fn modulus_curve_overrides_estimate() {
+        let mut material = metal(70e9, 23e-6);
+        assert!((youngs_modulus_at(&material, "Plate", 120.0).unwrap() - 68_180.0).abs() < 1.0);
+
+        let curve = Curve::new(vec![(20.0, 70e9), (200.0, 60e9)], OutOfRange::Error).unwrap();
+        material.set_curve(Property::YoungsModulus, curve);
+        assert!((youngs_modulus_at(&material, "Plate", 110.0).unwrap() - 65_000.0).abs() < 1e-6);
+        assert!(matches!(
+            youngs_modulus_at(&material, "Plate", -40.0),
+            Err(AnalysisError::OutOfRange { .. })
+        ));
+    }
+
+    #[test]
+    fn matching_materials_only_change_with_stiffness() {
+        let uniform = change(metal(205e9, 11.5e-6), Temperatures::uniform(200.0));
+        // Same α on both sides, the joint only softens and carries less force
+        assert!((uniform.bolt_expansion - uniform.plate_expansion).abs() < 1e-12);
+        assert!(uniform.loss > 0.0 && uniform.loss < 0.1 * 20_000.0);
+    }
+}
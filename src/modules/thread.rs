@@ -1,3 +1,16 @@
+use std::f64::consts::PI;
+
+/// Basic profile factors of the 60° thread, multiples of the pitch (ISO 68-1, ASME B1.1)
+const PITCH_DIAMETER: f64 = 0.649519; // d - d2 = 3/4·H
+const INTERNAL_MINOR: f64 = 1.082532; // d - D1 = 5/4·H
+const ISO_ROOT: f64 = 1.226869; // d - d3, ISO 898-1 root with r = H/6
+const UNR_ROOT: f64 = 1.190785; // d - d3, UNR rounded root
+const UN_STRESS: f64 = 0.974279; // d - d_S, ASME B1.1 stress area diameter
+
+/// Basic profile factors of the 30° and 29° power threads (ISO 2904, ASME B1.5)
+const POWER_PITCH_DIAMETER: f64 = 0.5; // d - d2
+const POWER_INTERNAL_MINOR: f64 = 1.0; // d - D1
+
 /// Unit system for threads
 #[derive(serde::Deserialize, serde::Serialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum Unit {
@@ -8,11 +21,13 @@ pub enum Unit {
 
 /// Thread form (standard profiles)
 #[derive(serde::Deserialize, serde::Serialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[allow(clippy::upper_case_acronyms)]
 pub enum ThreadForm {
     #[default]
     ISO, // Metric ISO 68-1
     UNC,         // Unified Coarse
     UNF,         // Unified Fine
+    UNEF,        // Unified Extra Fine
     Acme,        // Acme trapezoidal
     Trapezoidal, // ISO trapezoidal
     Custom,
@@ -52,7 +67,7 @@ impl Thread {
         hand: ThreadHand,
         tolerance_class: Option<String>,
     ) -> Self {
-        let minor_diameter = major_diameter - 1.226869 * pitch; // approximate ISO 68-1 formula
+        let minor_diameter = major_diameter - ISO_ROOT * pitch;
         Self {
             unit: Unit::Metric,
             form: ThreadForm::ISO,
@@ -78,7 +93,7 @@ impl Thread {
         tolerance_class: Option<String>,
     ) -> Self {
         let pitch = 1.0 / threads_per_inch;
-        let minor_diameter = major_diameter - 0.6495 * pitch; // approximate root for 60° thread
+        let minor_diameter = major_diameter - UNR_ROOT * pitch;
         Self {
             unit: Unit::Imperial,
             form,
@@ -94,11 +109,123 @@ impl Thread {
         }
     }
 
+    /// Create a new ISO metric trapezoidal thread (ISO 2904)
+    pub fn new_trapezoidal(
+        major_diameter: f64,
+        pitch: f64,
+        length: Option<f64>,
+        hand: ThreadHand,
+        tolerance_class: Option<String>,
+    ) -> Self {
+        // Crest clearance a_c grows with the pitch
+        let clearance = match pitch {
+            p if p <= 1.5 => 0.15,
+            p if p <= 5.0 => 0.25,
+            p if p <= 12.0 => 0.5,
+            _ => 1.0,
+        };
+        let minor_diameter = major_diameter - pitch - 2.0 * clearance;
+        Self {
+            unit: Unit::Metric,
+            form: ThreadForm::Trapezoidal,
+            major_diameter,
+            minor_diameter,
+            pitch,
+            threads_per_unit: None,
+            length,
+            hand,
+            angle: 30.0,
+            tolerance_class,
+            note: None,
+        }
+    }
+
+    /// Create a new general purpose Acme thread (ASME B1.5)
+    pub fn new_acme(
+        major_diameter: f64,
+        threads_per_inch: f64,
+        length: Option<f64>,
+        hand: ThreadHand,
+        tolerance_class: Option<String>,
+    ) -> Self {
+        let pitch = 1.0 / threads_per_inch;
+        let clearance = if threads_per_inch > 10.0 {
+            0.010
+        } else {
+            0.020
+        };
+        let minor_diameter = major_diameter - pitch - 2.0 * clearance;
+        Self {
+            unit: Unit::Imperial,
+            form: ThreadForm::Acme,
+            major_diameter,
+            minor_diameter,
+            pitch,
+            threads_per_unit: Some(threads_per_inch),
+            length,
+            hand,
+            angle: 29.0,
+            tolerance_class,
+            note: None,
+        }
+    }
+
+    /// True for the 30° and 29° power transmission profiles
+    pub fn is_power_thread(&self) -> bool {
+        matches!(self.form, ThreadForm::Trapezoidal | ThreadForm::Acme)
+    }
+
     /// Returns the pitch in the correct unit
     pub fn get_pitch(&self) -> f64 {
         self.pitch
     }
 
+    /// Pitch diameter d2, equal to D2 for the basic profile
+    pub fn pitch_diameter(&self) -> f64 {
+        let factor = if self.is_power_thread() {
+            POWER_PITCH_DIAMETER
+        } else {
+            PITCH_DIAMETER
+        };
+        self.major_diameter - factor * self.pitch
+    }
+
+    /// Minor diameter of the internal thread D1
+    pub fn internal_minor_diameter(&self) -> f64 {
+        let factor = if self.is_power_thread() {
+            POWER_INTERNAL_MINOR
+        } else {
+            INTERNAL_MINOR
+        };
+        self.major_diameter - factor * self.pitch
+    }
+
+    /// Minor diameter of the external thread d3
+    pub fn external_minor_diameter(&self) -> f64 {
+        self.minor_diameter
+    }
+
+    /// Diameter of the stress area d_S
+    pub fn stress_diameter(&self) -> f64 {
+        match self.form {
+            ThreadForm::UNC | ThreadForm::UNF | ThreadForm::UNEF => {
+                self.major_diameter - UN_STRESS * self.pitch
+            }
+            _ => (self.pitch_diameter() + self.external_minor_diameter()) / 2.0,
+        }
+    }
+
+    /// Tensile stress area A_s = π/4·((d2 + d3)/2)² (ISO 898-1), or
+    /// A_s = π/4·(d - 0.9743·P)² for unified threads (ASME B1.1)
+    pub fn stress_area(&self) -> f64 {
+        PI / 4.0 * self.stress_diameter().powi(2)
+    }
+
+    /// Flank angle β/2 in degrees, half the included thread angle
+    pub fn flank_angle(&self) -> f64 {
+        self.angle / 2.0
+    }
+
     /// Returns the thread depth (approximate)
     pub fn depth(&self) -> f64 {
         (self.major_diameter - self.minor_diameter) / 2.0
@@ -109,3 +236,35 @@ impl Thread {
         self.note = Some(note.into());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn iso_stress_areas() {
+        // ISO 898-1 Table 4, tabulated values are rounded
+        for (d, p, a_s) in [(10.0, 1.5, 58.0), (12.0, 1.75, 84.3), (16.0, 1.5, 167.0)] {
+            let thread = Thread::new_metric(d, p, None, ThreadHand::Right, None);
+            assert!((thread.stress_area() - a_s).abs() < 0.5, "M{d}x{p}");
+        }
+    }
+
+    #[test]
+    fn iso_profile() {
+        let thread = Thread::new_metric(12.0, 1.75, None, ThreadHand::Right, None);
+        assert!((thread.pitch_diameter() - 10.863).abs() < 1e-3);
+        assert!((thread.internal_minor_diameter() - 10.106).abs() < 1e-3);
+        assert!((thread.external_minor_diameter() - 9.853).abs() < 1e-3);
+        assert_eq!(thread.flank_angle(), 30.0);
+    }
+
+    #[test]
+    fn unified_stress_areas() {
+        // ASME B1.1 Table 5A / 5B, square inches
+        for (d, n, a_s) in [(0.5, 13.0, 0.1419), (0.5, 20.0, 0.1599), (1.0, 8.0, 0.6057)] {
+            let thread = Thread::new_imperial(d, n, None, ThreadHand::Right, ThreadForm::UNC, None);
+            assert!((thread.stress_area() - a_s).abs() < 5e-4, "{d}-{n}");
+        }
+    }
+}
@@ -0,0 +1,499 @@
+use std::fmt;
+
+use crate::modules::thread::{Thread, ThreadForm, ThreadHand, Unit};
+
+/// ISO 261 coarse pitch series, (d, P) in mm
+const ISO_COARSE: &[(f64, f64)] = &[
+    (1.6, 0.35),
+    (2.0, 0.4),
+    (2.5, 0.45),
+    (3.0, 0.5),
+    (4.0, 0.7),
+    (5.0, 0.8),
+    (6.0, 1.0),
+    (8.0, 1.25),
+    (10.0, 1.5),
+    (12.0, 1.75),
+    (14.0, 2.0),
+    (16.0, 2.0),
+    (18.0, 2.5),
+    (20.0, 2.5),
+    (22.0, 2.5),
+    (24.0, 3.0),
+    (27.0, 3.0),
+    (30.0, 3.5),
+    (33.0, 3.5),
+    (36.0, 4.0),
+    (39.0, 4.0),
+    (42.0, 4.5),
+    (45.0, 4.5),
+    (48.0, 5.0),
+    (52.0, 5.0),
+    (56.0, 5.5),
+    (60.0, 5.5),
+    (64.0, 6.0),
+];
+
+/// ISO 262 selected fine pitches, (d, P) in mm
+const ISO_FINE: &[(f64, f64)] = &[
+    (8.0, 1.0),
+    (10.0, 1.25),
+    (10.0, 1.0),
+    (12.0, 1.5),
+    (12.0, 1.25),
+    (14.0, 1.5),
+    (16.0, 1.5),
+    (18.0, 2.0),
+    (18.0, 1.5),
+    (20.0, 2.0),
+    (20.0, 1.5),
+    (22.0, 2.0),
+    (22.0, 1.5),
+    (24.0, 2.0),
+    (27.0, 2.0),
+    (30.0, 2.0),
+    (33.0, 2.0),
+    (36.0, 3.0),
+    (39.0, 3.0),
+    (42.0, 3.0),
+    (45.0, 3.0),
+    (48.0, 3.0),
+    (52.0, 4.0),
+    (56.0, 4.0),
+    (60.0, 4.0),
+    (64.0, 4.0),
+];
+
+/// ISO 2904 trapezoidal threads, (d, P) in mm
+const TRAPEZOIDAL: &[(f64, f64)] = &[
+    (8.0, 1.5),
+    (10.0, 2.0),
+    (12.0, 3.0),
+    (14.0, 3.0),
+    (16.0, 4.0),
+    (18.0, 4.0),
+    (20.0, 4.0),
+    (22.0, 5.0),
+    (24.0, 5.0),
+    (26.0, 5.0),
+    (28.0, 5.0),
+    (30.0, 6.0),
+    (32.0, 6.0),
+    (36.0, 6.0),
+    (40.0, 7.0),
+    (44.0, 7.0),
+    (48.0, 8.0),
+    (52.0, 8.0),
+    (60.0, 9.0),
+];
+
+/// ASME B1.1 unified series, (size, d, TPI) in inches
+const UNC: &[(&str, f64, f64)] = &[
+    ("#4", 0.112, 40.0),
+    ("#5", 0.125, 40.0),
+    ("#6", 0.138, 32.0),
+    ("#8", 0.164, 32.0),
+    ("#10", 0.190, 24.0),
+    ("#12", 0.216, 24.0),
+    ("1/4", 0.25, 20.0),
+    ("5/16", 0.3125, 18.0),
+    ("3/8", 0.375, 16.0),
+    ("7/16", 0.4375, 14.0),
+    ("1/2", 0.5, 13.0),
+    ("9/16", 0.5625, 12.0),
+    ("5/8", 0.625, 11.0),
+    ("3/4", 0.75, 10.0),
+    ("7/8", 0.875, 9.0),
+    ("1", 1.0, 8.0),
+    ("1 1/8", 1.125, 7.0),
+    ("1 1/4", 1.25, 7.0),
+    ("1 3/8", 1.375, 6.0),
+    ("1 1/2", 1.5, 6.0),
+    ("1 3/4", 1.75, 5.0),
+    ("2", 2.0, 4.5),
+];
+
+const UNF: &[(&str, f64, f64)] = &[
+    ("#4", 0.112, 48.0),
+    ("#5", 0.125, 44.0),
+    ("#6", 0.138, 40.0),
+    ("#8", 0.164, 36.0),
+    ("#10", 0.190, 32.0),
+    ("#12", 0.216, 28.0),
+    ("1/4", 0.25, 28.0),
+    ("5/16", 0.3125, 24.0),
+    ("3/8", 0.375, 24.0),
+    ("7/16", 0.4375, 20.0),
+    ("1/2", 0.5, 20.0),
+    ("9/16", 0.5625, 18.0),
+    ("5/8", 0.625, 18.0),
+    ("3/4", 0.75, 16.0),
+    ("7/8", 0.875, 14.0),
+    ("1", 1.0, 12.0),
+    ("1 1/8", 1.125, 12.0),
+    ("1 1/4", 1.25, 12.0),
+    ("1 3/8", 1.375, 12.0),
+    ("1 1/2", 1.5, 12.0),
+];
+
+const UNEF: &[(&str, f64, f64)] = &[
+    ("#12", 0.216, 32.0),
+    ("1/4", 0.25, 32.0),
+    ("5/16", 0.3125, 32.0),
+    ("3/8", 0.375, 32.0),
+    ("7/16", 0.4375, 28.0),
+    ("1/2", 0.5, 28.0),
+    ("9/16", 0.5625, 24.0),
+    ("5/8", 0.625, 24.0),
+    ("11/16", 0.6875, 24.0),
+    ("3/4", 0.75, 20.0),
+    ("13/16", 0.8125, 20.0),
+    ("7/8", 0.875, 20.0),
+    ("15/16", 0.9375, 20.0),
+    ("1", 1.0, 20.0),
+];
+
+/// ASME B1.5 general purpose Acme, (size, d, TPI) in inches
+const ACME: &[(&str, f64, f64)] = &[
+    ("1/4", 0.25, 16.0),
+    ("5/16", 0.3125, 14.0),
+    ("3/8", 0.375, 12.0),
+    ("7/16", 0.4375, 12.0),
+    ("1/2", 0.5, 10.0),
+    ("5/8", 0.625, 8.0),
+    ("3/4", 0.75, 6.0),
+    ("7/8", 0.875, 6.0),
+    ("1", 1.0, 5.0),
+    ("1 1/4", 1.25, 5.0),
+    ("1 1/2", 1.5, 4.0),
+    ("1 3/4", 1.75, 4.0),
+    ("2", 2.0, 4.0),
+];
+
+const TOLERANCE: f64 = 1e-6;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DesignationError {
+    Empty,
+    Invalid(String),
+    UnknownSize(String),
+}
+
+impl fmt::Display for DesignationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "no thread designation given"),
+            Self::Invalid(text) => write!(f, "\"{text}\" is not a thread designation"),
+            Self::UnknownSize(text) => write!(f, "\"{text}\" needs a pitch, size not in ISO 261"),
+        }
+    }
+}
+
+impl std::error::Error for DesignationError {}
+
+fn unified_series(form: ThreadForm) -> &'static [(&'static str, f64, f64)] {
+    match form {
+        ThreadForm::UNC => UNC,
+        ThreadForm::UNF => UNF,
+        ThreadForm::UNEF => UNEF,
+        ThreadForm::Acme => ACME,
+        _ => &[],
+    }
+}
+
+fn metric(d: f64, p: f64) -> Thread {
+    Thread::new_metric(d, p, None, ThreadHand::Right, None)
+}
+
+fn trapezoidal(d: f64, p: f64) -> Thread {
+    Thread::new_trapezoidal(d, p, None, ThreadHand::Right, None)
+}
+
+fn imperial(form: ThreadForm, d: f64, tpi: f64) -> Thread {
+    match form {
+        ThreadForm::Acme => Thread::new_acme(d, tpi, None, ThreadHand::Right, None),
+        _ => Thread::new_imperial(d, tpi, None, ThreadHand::Right, form, None),
+    }
+}
+
+fn same(a: f64, b: f64) -> bool {
+    (a - b).abs() < TOLERANCE
+}
+
+/// Every catalogued thread of one form
+pub fn series(form: ThreadForm) -> Vec<Thread> {
+    match form {
+        ThreadForm::ISO => ISO_COARSE
+            .iter()
+            .chain(ISO_FINE)
+            .map(|&(d, p)| metric(d, p))
+            .collect(),
+        ThreadForm::Trapezoidal => TRAPEZOIDAL
+            .iter()
+            .map(|&(d, p)| trapezoidal(d, p))
+            .collect(),
+        ThreadForm::Custom => Vec::new(),
+        _ => unified_series(form)
+            .iter()
+            .map(|&(_, d, tpi)| imperial(form, d, tpi))
+            .collect(),
+    }
+}
+
+/// ISO 261 coarse thread for a nominal diameter in mm
+pub fn coarse(diameter: f64) -> Option<Thread> {
+    ISO_COARSE
+        .iter()
+        .find(|&&(d, _)| same(d, diameter))
+        .map(|&(d, p)| metric(d, p))
+}
+
+/// ISO 262 fine pitches for a nominal diameter in mm, coarsest first
+pub fn fine(diameter: f64) -> Vec<Thread> {
+    ISO_FINE
+        .iter()
+        .filter(|&&(d, _)| same(d, diameter))
+        .map(|&(d, p)| metric(d, p))
+        .collect()
+}
+
+/// True if the thread's form, diameter and pitch appear in the catalogue
+pub fn is_standard(thread: &Thread) -> bool {
+    series(thread.form)
+        .iter()
+        .any(|t| same(t.major_diameter, thread.major_diameter) && same(t.pitch, thread.pitch))
+}
+
+/// Formats a thread the way it is written on a drawing, e.g. "M12x1.25" or "1/2-13 UNC"
+pub fn designation(thread: &Thread) -> String {
+    let hand = match thread.hand {
+        ThreadHand::Right => "",
+        ThreadHand::Left => " LH",
+    };
+
+    match thread.unit {
+        Unit::Metric => {
+            let prefix = match thread.form {
+                ThreadForm::Trapezoidal => "Tr",
+                _ => "M",
+            };
+            let pitch = match coarse(thread.major_diameter) {
+                Some(c) if thread.form == ThreadForm::ISO && same(c.pitch, thread.pitch) => {
+                    String::new()
+                }
+                _ => format!("x{}", thread.pitch),
+            };
+            format!("{prefix}{}{pitch}{hand}", thread.major_diameter)
+        }
+        Unit::Imperial => {
+            let tpi = thread.threads_per_unit.unwrap_or(1.0 / thread.pitch);
+            let size = [UNC, UNF, UNEF, ACME]
+                .iter()
+                .flat_map(|series| series.iter())
+                .find(|&&(_, d, _)| same(d, thread.major_diameter))
+                .map_or_else(
+                    || format!("{}", thread.major_diameter),
+                    |&(s, _, _)| s.to_string(),
+                );
+            let series = match thread.form {
+                ThreadForm::UNC => " UNC",
+                ThreadForm::UNF => " UNF",
+                ThreadForm::UNEF => " UNEF",
+                ThreadForm::Acme => " ACME",
+                _ => "",
+            };
+            format!("{size}-{tpi}{series}{hand}")
+        }
+    }
+}
+
+fn number(text: &str, original: &str) -> Result<f64, DesignationError> {
+    text.trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|v| *v > 0.0)
+        .ok_or_else(|| DesignationError::Invalid(original.to_string()))
+}
+
+/// Inch size as a number gauge ("#10", "No. 10"), fraction ("1/2", "1 1/4", "1-1/4") or decimal
+fn inch_size(text: &str, original: &str) -> Result<f64, DesignationError> {
+    let text = text.trim();
+    if let Some(gauge) = text
+        .strip_prefix('#')
+        .or_else(|| text.strip_prefix("NO."))
+        .or_else(|| text.strip_prefix("NO"))
+    {
+        return Ok(0.060 + 0.013 * number(gauge, original)?);
+    }
+
+    let (whole, fraction) = match text.split_once(['-', ' ']) {
+        Some((whole, fraction)) if fraction.contains('/') => (number(whole, original)?, fraction),
+        _ if text.contains('/') => (0.0, text),
+        _ => return number(text, original),
+    };
+    let (num, den) = fraction
+        .split_once('/')
+        .ok_or_else(|| DesignationError::Invalid(original.to_string()))?;
+    Ok(whole + number(num, original)? / number(den, original)?)
+}
+
+/// Parses a designation typed by the user into a thread. Pitches outside the
+/// catalogue are accepted, check them with [`is_standard`].
+///
+/// Accepted forms: "M12", "M12x1.25", "M12 x 1.25-6g", "Tr20x4 LH", "1/2-13 UNC",
+/// "#10-32 UNF", "1 1/4-7 UNC-2A", "1/2-10 ACME"
+pub fn parse(text: &str) -> Result<Thread, DesignationError> {
+    let original = text.trim();
+    if original.is_empty() {
+        return Err(DesignationError::Empty);
+    }
+    let invalid = || DesignationError::Invalid(original.to_string());
+
+    let mut text = original.to_uppercase();
+    let mut hand = ThreadHand::Right;
+    for suffix in [" LH", "-LH", "LH"] {
+        if let Some(stripped) = text.strip_suffix(suffix) {
+            text = stripped.trim().to_string();
+            hand = ThreadHand::Left;
+            break;
+        }
+    }
+
+    let mut thread = if let Some(rest) = text.strip_prefix("TR") {
+        let (size, pitch) = rest.split_once('X').ok_or_else(invalid)?;
+        let (pitch, tolerance) = split_tolerance(pitch);
+        let mut thread = trapezoidal(number(size, original)?, number(pitch, original)?);
+        thread.tolerance_class = tolerance;
+        thread
+    } else if let Some(rest) = text.strip_prefix('M') {
+        let (body, tolerance) = split_tolerance(rest);
+        let mut thread = match body.split_once('X') {
+            Some((size, pitch)) => metric(number(size, original)?, number(pitch, original)?),
+            None => coarse(number(body, original)?)
+                .ok_or_else(|| DesignationError::UnknownSize(original.to_string()))?,
+        };
+        thread.tolerance_class = tolerance;
+        thread
+    } else {
+        parse_inch(&text, original)?
+    };
+
+    thread.hand = hand;
+    Ok(thread)
+}
+
+/// Splits a trailing tolerance class such as "-6g" or "-2A" from the designation
+fn split_tolerance(text: &str) -> (&str, Option<String>) {
+    match text.rsplit_once('-') {
+        Some((body, class))
+            if class.starts_with(|c: char| c.is_ascii_digit())
+                && class.ends_with(|c: char| c.is_ascii_alphabetic()) =>
+        {
+            (body.trim(), Some(class.to_lowercase()))
+        }
+        _ => (text.trim(), None),
+    }
+}
+
+fn parse_inch(text: &str, original: &str) -> Result<Thread, DesignationError> {
+    let invalid = || DesignationError::Invalid(original.to_string());
+
+    let (body, form) = [
+        ("UNEF", ThreadForm::UNEF),
+        ("UNC", ThreadForm::UNC),
+        ("UNF", ThreadForm::UNF),
+        ("ACME", ThreadForm::Acme),
+    ]
+    .into_iter()
+    .find_map(|(name, form)| {
+        text.find(name)
+            .map(|i| ((&text[..i], &text[i + name.len()..]), Some(form)))
+    })
+    .unwrap_or(((text, ""), None));
+    let (body, rest) = body;
+    let tolerance = split_tolerance(&format!("0{rest}")).1;
+
+    let (size, tpi) = body.trim().rsplit_once('-').ok_or_else(invalid)?;
+    let (d, tpi) = (inch_size(size, original)?, number(tpi, original)?);
+
+    // Without a series suffix the pitch decides between coarse, fine and extra fine
+    let form = form
+        .or_else(|| {
+            [ThreadForm::UNC, ThreadForm::UNF, ThreadForm::UNEF]
+                .into_iter()
+                .find(|&form| {
+                    unified_series(form)
+                        .iter()
+                        .any(|&(_, sd, st)| same(sd, d) && same(st, tpi))
+                })
+        })
+        .unwrap_or(ThreadForm::UNC);
+
+    let mut thread = imperial(form, d, tpi);
+    thread.tolerance_class = tolerance.map(|t| t.to_uppercase());
+    Ok(thread)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_metric() {
+        let thread = parse("M12").unwrap();
+        assert_eq!((thread.major_diameter, thread.pitch), (12.0, 1.75));
+
+        let thread = parse("m12 x 1.25-6g").unwrap();
+        assert_eq!((thread.major_diameter, thread.pitch), (12.0, 1.25));
+        assert_eq!(thread.tolerance_class.as_deref(), Some("6g"));
+
+        let thread = parse("M16x1.5 LH").unwrap();
+        assert_eq!(thread.hand, ThreadHand::Left);
+        assert_eq!(designation(&thread), "M16x1.5 LH");
+
+        assert_eq!(
+            parse("M13").unwrap_err(),
+            DesignationError::UnknownSize("M13".into())
+        );
+        assert!(parse("M12x1.3").is_ok());
+        assert!(!is_standard(&parse("M12x1.3").unwrap()));
+    }
+
+    #[test]
+    fn parses_unified() {
+        let thread = parse("1/2-13 UNC").unwrap();
+        assert_eq!(thread.form, ThreadForm::UNC);
+        assert_eq!(thread.major_diameter, 0.5);
+
+        let thread = parse("#10-32").unwrap();
+        assert_eq!(thread.form, ThreadForm::UNF);
+        assert!((thread.major_diameter - 0.19).abs() < 1e-9);
+
+        let thread = parse("1 1/4-7 UNC-2A").unwrap();
+        assert_eq!(thread.major_diameter, 1.25);
+        assert_eq!(thread.tolerance_class.as_deref(), Some("2A"));
+        assert_eq!(designation(&thread), "1 1/4-7 UNC");
+
+        assert_eq!(parse("1-1/4-12").unwrap().form, ThreadForm::UNF);
+        assert_eq!(parse("1/2-28").unwrap().form, ThreadForm::UNEF);
+    }
+
+    #[test]
+    fn parses_power_threads() {
+        let thread = parse("Tr20x4").unwrap();
+        assert_eq!(thread.form, ThreadForm::Trapezoidal);
+        assert_eq!(thread.pitch_diameter(), 18.0);
+        assert_eq!(thread.minor_diameter, 15.5);
+
+        assert_eq!(parse("1/2-10 ACME").unwrap().form, ThreadForm::Acme);
+        assert!(parse("bolt").is_err());
+    }
+
+    #[test]
+    fn fine_pitches() {
+        let pitches: Vec<f64> = fine(16.0).iter().map(|t| t.pitch).collect();
+        assert_eq!(pitches, vec![1.5]);
+        assert_eq!(fine(12.0).len(), 2);
+        assert!(fine(12.0).iter().all(is_standard));
+    }
+}
@@ -0,0 +1,385 @@
+use std::f64::consts::PI;
+
+use crate::modules::{analysis::Section, property_class, thread::Thread, thread_catalogue};
+
+/// Ratio of the upper to the lower yield point assumed for yield controlled tightening
+const YIELD_SCATTER: f64 = 1.2;
+
+/// How the assembly preload is controlled (VDI 2230 table A8)
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, Copy, PartialEq)]
+pub enum TighteningMethod {
+    Torque, // torque wrench, scatter covered by αA
+    Angle {
+        snug_torque: f64, // N·m applied before the angle is counted
+        angle: f64,       // degrees turned after snugging
+    },
+    Yield, // yield point controlled, ν = 1
+}
+
+impl Default for TighteningMethod {
+    fn default() -> Self {
+        Self::Torque
+    }
+}
+
+/// Friction coefficient ranges in the thread and under the turned part
+#[derive(Debug, Clone, Copy)]
+pub struct Friction {
+    pub thread_min: f64, // μG min
+    pub thread_max: f64, // μG max
+    pub head_min: f64,   // μK min
+    pub head_max: f64,   // μK max
+}
+
+/// Split of the tightening torque into its parts (N·m)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TorqueBreakdown {
+    pub pitch: f64,           // raising the load along the helix
+    pub thread_friction: f64, // μG term
+    pub head_friction: f64,   // μK term
+    pub prevailing: f64,      // M_ü of a prevailing torque nut
+}
+
+impl TorqueBreakdown {
+    pub fn total(&self) -> f64 {
+        self.pitch + self.thread_friction + self.head_friction + self.prevailing
+    }
+}
+
+/// Assembly preload range reached by a tightening method (N, N·m)
+#[derive(Debug, Clone, Copy)]
+pub struct AssemblyPreload {
+    pub torque: TorqueBreakdown, // torque to specify, or the expected final torque
+    pub preload_min: f64,        // F_Mmin reached at the upper friction bound
+    pub preload_max: f64,        // F_Mmax reached at the lower friction bound
+}
+
+impl AssemblyPreload {
+    /// Effective tightening factor αA = F_Mmax / F_Mmin
+    pub fn scatter(&self) -> f64 {
+        self.preload_max / self.preload_min
+    }
+}
+
+/// Mean friction diameter D_Km of an annular bearing face (mm)
+pub fn friction_diameter(outer: f64, inner: f64) -> f64 {
+    (outer + inner) / 2.0
+}
+
+/// Torque lever per unit preload (mm), P/2π + d2/2·μG/cos(β/2) + D_Km/2·μK
+fn lever(
+    section: &Section,
+    mu_thread: f64,
+    mu_head: f64,
+    friction_diameter: f64,
+) -> (f64, f64, f64) {
+    (
+        section.p / (2.0 * PI),
+        section.d2 / (2.0 * section.flank.cos()) * mu_thread,
+        friction_diameter / 2.0 * mu_head,
+    )
+}
+
+/// Tightening torque M_A for a preload, 0.16·P + 0.58·d2·μG + D_Km/2·μK for 60° threads
+pub fn tightening_torque(
+    section: &Section,
+    preload: f64,
+    mu_thread: f64,
+    mu_head: f64,
+    friction_diameter: f64,
+    prevailing: f64,
+) -> TorqueBreakdown {
+    let (pitch, thread, head) = lever(section, mu_thread, mu_head, friction_diameter);
+    TorqueBreakdown {
+        pitch: preload * pitch / 1000.0,
+        thread_friction: preload * thread / 1000.0,
+        head_friction: preload * head / 1000.0,
+        prevailing,
+    }
+}
+
+/// Preload produced by a tightening torque, the inverse of `tightening_torque`
+pub fn preload_from_torque(
+    section: &Section,
+    torque: f64,
+    mu_thread: f64,
+    mu_head: f64,
+    friction_diameter: f64,
+    prevailing: f64,
+) -> f64 {
+    let (pitch, thread, head) = lever(section, mu_thread, mu_head, friction_diameter);
+    ((torque - prevailing) * 1000.0 / (pitch + thread + head)).max(0.0)
+}
+
+/// Preload at which the equivalent stress reaches ν·R_p0.2 (VDI 2230 R7, F_Mzul)
+pub fn yield_preload(
+    section: &Section,
+    yield_strength: f64,
+    mu_thread: f64,
+    utilisation: f64,
+) -> f64 {
+    let d_0 = section.min_diameter();
+    let thread_term = section.p / (PI * section.d2) + mu_thread / section.flank.cos();
+    section.min_area() * utilisation * yield_strength
+        / (1.0 + 3.0 * (1.5 * section.d2 / d_0 * thread_term).powi(2)).sqrt()
+}
+
+/// Preload added by turning the nut through an angle after snugging, F = θ/360°·P/(δS+δP)
+pub fn angle_preload(section: &Section, angle: f64, resilience: f64) -> f64 {
+    angle / 360.0 * section.p / resilience
+}
+
+/// Preload range for a tightening method
+///
+/// `alpha_a` only applies to torque control, where the minimum is the lower of the
+/// friction scatter and F_Mzul/αA. `resilience` is δS + δP (mm/N).
+#[allow(clippy::too_many_arguments)]
+pub fn assembly_preload(
+    method: TighteningMethod,
+    section: &Section,
+    friction: &Friction,
+    yield_strength: f64,
+    utilisation: f64,
+    alpha_a: f64,
+    friction_diameter: f64,
+    prevailing: f64,
+    resilience: f64,
+) -> AssemblyPreload {
+    let yield_min = yield_preload(section, yield_strength, friction.thread_max, 1.0);
+    let yield_max = yield_preload(
+        section,
+        YIELD_SCATTER * yield_strength,
+        friction.thread_min,
+        1.0,
+    );
+
+    match method {
+        TighteningMethod::Torque => {
+            let preload_max =
+                yield_preload(section, yield_strength, friction.thread_min, utilisation);
+            let torque = tightening_torque(
+                section,
+                preload_max,
+                friction.thread_min,
+                friction.head_min,
+                friction_diameter,
+                prevailing,
+            );
+            let preload_min = preload_from_torque(
+                section,
+                torque.total(),
+                friction.thread_max,
+                friction.head_max,
+                friction_diameter,
+                prevailing,
+            )
+            .min(preload_max / alpha_a);
+            AssemblyPreload {
+                torque,
+                preload_min,
+                preload_max,
+            }
+        }
+        TighteningMethod::Angle { snug_torque, angle } => {
+            let snug = |mu_thread, mu_head| {
+                preload_from_torque(
+                    section,
+                    snug_torque,
+                    mu_thread,
+                    mu_head,
+                    friction_diameter,
+                    prevailing,
+                )
+            };
+            let turned = angle_preload(section, angle, resilience);
+            let preload_min =
+                (snug(friction.thread_max, friction.head_max) + turned).min(yield_min);
+            let preload_max =
+                (snug(friction.thread_min, friction.head_min) + turned).min(yield_max);
+            AssemblyPreload {
+                torque: tightening_torque(
+                    section,
+                    preload_max,
+                    friction.thread_min,
+                    friction.head_min,
+                    friction_diameter,
+                    prevailing,
+                ),
+                preload_min,
+                preload_max,
+            }
+        }
+        TighteningMethod::Yield => AssemblyPreload {
+            torque: tightening_torque(
+                section,
+                yield_max,
+                friction.thread_min,
+                friction.head_min,
+                friction_diameter,
+                prevailing,
+            ),
+            preload_min: yield_min,
+            preload_max: yield_max,
+        },
+    }
+}
+
+/// Width across flats and medium clearance hole of hexagon heads (ISO 4017, ISO 273)
+#[rustfmt::skip]
+const HEX_BEARING: &[(f64, f64, f64)] = &[
+    (3.0, 5.5, 3.4), (4.0, 7.0, 4.5), (5.0, 8.0, 5.5), (6.0, 10.0, 6.6),
+    (8.0, 13.0, 9.0), (10.0, 16.0, 11.0), (12.0, 18.0, 13.5), (14.0, 21.0, 15.5),
+    (16.0, 24.0, 17.5), (18.0, 27.0, 20.0), (20.0, 30.0, 22.0), (22.0, 34.0, 24.0),
+    (24.0, 36.0, 26.0), (27.0, 41.0, 30.0), (30.0, 46.0, 33.0), (33.0, 50.0, 36.0),
+    (36.0, 55.0, 39.0),
+];
+
+/// Bearing outer and hole diameter of a hexagon head, estimated outside the table (mm)
+fn hex_bearing(diameter: f64) -> (f64, f64) {
+    HEX_BEARING
+        .iter()
+        .find(|(d, _, _)| (d - diameter).abs() < 1e-6)
+        .map(|&(_, s, d_h)| (s, d_h))
+        .unwrap_or((1.5 * diameter, 1.1 * diameter))
+}
+
+/// One line of a workshop torque sheet (N, N·m)
+#[derive(Debug, Clone)]
+pub struct TorqueRow {
+    pub designation: String,
+    pub stress_area: f64, // A_s mm²
+    pub preload: f64,     // F_Mzul
+    pub torque: f64,      // M_A
+}
+
+/// Torque table for hexagon head bolts of one property class, skipping sizes it does not cover
+pub fn torque_table(
+    threads: &[Thread],
+    designation: &str,
+    mu_thread: f64,
+    mu_head: f64,
+    utilisation: f64,
+) -> Vec<TorqueRow> {
+    threads
+        .iter()
+        .filter_map(|thread| {
+            let section = Section::from_thread(thread);
+            let class = property_class::bolt_class(designation, section.d)?;
+            let (outer, inner) = hex_bearing(section.d);
+            let preload = yield_preload(&section, class.yield_strength, mu_thread, utilisation);
+            let torque = tightening_torque(
+                &section,
+                preload,
+                mu_thread,
+                mu_head,
+                friction_diameter(outer, inner),
+                0.0,
+            );
+            Some(TorqueRow {
+                designation: thread_catalogue::designation(thread),
+                stress_area: section.stress_area(),
+                preload,
+                torque: torque.total(),
+            })
+        })
+        .collect()
+}
+
+/// Formats a torque table as CSV for the workshop sheets
+pub fn table_csv(rows: &[TorqueRow]) -> String {
+    let mut csv = String::from("Thread,A_s (mm²),F_M (kN),M_A (N·m)\n");
+    for row in rows {
+        csv.push_str(&format!(
+            "{},{:.1},{:.1},{:.1}\n",
+            row.designation,
+            row.stress_area,
+            row.preload / 1000.0,
+            row.torque
+        ));
+    }
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::thread::{ThreadForm, ThreadHand};
+
+    fn m12() -> Section {
+        Section::from_thread(&Thread::new_metric(
+            12.0,
+            1.75,
+            None,
+            ThreadHand::Right,
+            None,
+        ))
+    }
+
+    fn friction() -> Friction {
+        Friction {
+            thread_min: 0.10,
+            thread_max: 0.14,
+            head_min: 0.10,
+            head_max: 0.14,
+        }
+    }
+
+    #[test]
+    fn torque_round_trips_to_preload() {
+        let section = m12();
+        let torque = tightening_torque(&section, 40_000.0, 0.12, 0.12, 15.75, 2.0);
+        // 0.16·P + 0.58·d2·μG + D_Km/2·μK
+        let simplified = 40.0 * (0.16 * 1.75 + 0.58 * section.d2 * 0.12 + 15.75 / 2.0 * 0.12) + 2.0;
+        assert!((torque.total() - simplified).abs() < 0.3);
+        let preload = preload_from_torque(&section, torque.total(), 0.12, 0.12, 15.75, 2.0);
+        assert!((preload - 40_000.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn methods_narrow_the_scatter() {
+        let section = m12();
+        let resilience = 4e-6;
+        let range = |method| {
+            assembly_preload(
+                method,
+                &section,
+                &friction(),
+                640.0,
+                0.9,
+                1.6,
+                15.75,
+                0.0,
+                resilience,
+            )
+        };
+        let torque = range(TighteningMethod::Torque);
+        let yielded = range(TighteningMethod::Yield);
+        let angle = range(TighteningMethod::Angle {
+            snug_torque: 10.0,
+            angle: 90.0,
+        });
+
+        assert!((torque.preload_max - 44_190.0).abs() < 10.0);
+        assert!(torque.scatter() > yielded.scatter());
+        assert!(yielded.preload_min > torque.preload_max);
+        assert!(angle.preload_min <= yielded.preload_min);
+        assert!(angle.preload_max <= yielded.preload_max);
+    }
+
+    #[test]
+    fn torque_table_for_8_8() {
+        let rows = torque_table(
+            &thread_catalogue::series(ThreadForm::ISO),
+            "8.8",
+            0.12,
+            0.12,
+            0.9,
+        );
+        let m12 = rows.iter().find(|row| row.designation == "M12").unwrap();
+        // VDI 2230 table A1 lists M_A = 85 N·m for M12 8.8 at μ = 0.12
+        assert!((m12.preload - 43_110.0).abs() < 10.0);
+        assert!((m12.torque - 85.0).abs() < 0.5);
+        assert!(rows.iter().any(|row| row.designation == "M36"));
+        assert_eq!(table_csv(&rows).lines().count(), rows.len() + 1);
+    }
+}
@@ -0,0 +1,100 @@
+use crate::modules::{
+    elements::{Washer, WasherType},
+    material::{Material, MaterialType},
+};
+
+const PA_PER_MPA: f64 = 1e6;
+
+/// ISO 7089 normal series plain washers, (d, d1, d2, h) in mm
+///
+/// ISO 7090 shares the dimensions with a chamfered outer edge.
+const ISO_7089: &[(f64, f64, f64, f64)] = &[
+    (3.0, 3.2, 7.0, 0.5),
+    (4.0, 4.3, 9.0, 0.8),
+    (5.0, 5.3, 10.0, 1.0),
+    (6.0, 6.4, 12.0, 1.6),
+    (8.0, 8.4, 16.0, 1.6),
+    (10.0, 10.5, 20.0, 2.0),
+    (12.0, 13.0, 24.0, 2.5),
+    (14.0, 15.0, 28.0, 2.5),
+    (16.0, 17.0, 30.0, 3.0),
+    (20.0, 21.0, 37.0, 3.0),
+    (24.0, 25.0, 44.0, 4.0),
+    (30.0, 31.0, 56.0, 4.0),
+    (36.0, 37.0, 66.0, 5.0),
+];
+
+/// Wedge locking washer pairs, (d, d1, d2, pair thickness) in mm
+const WEDGE_LOCK: &[(f64, f64, f64, f64)] = &[
+    (6.0, 6.4, 9.5, 1.8),
+    (8.0, 8.7, 13.5, 2.5),
+    (10.0, 10.7, 16.6, 2.5),
+    (12.0, 13.0, 19.5, 3.4),
+    (16.0, 17.0, 25.4, 3.4),
+    (20.0, 21.4, 30.7, 4.6),
+    (24.0, 25.3, 39.0, 6.6),
+];
+
+/// Washer steel of each type, (hardness HV, R_m MPa)
+fn steel(kind: WasherType) -> (u32, f64) {
+    match kind {
+        WasherType::Plain => (200, 640.0),
+        WasherType::WedgeLock => (465, 1500.0),
+        _ => (300, 960.0),
+    }
+}
+
+fn material(kind: WasherType) -> Material {
+    let (hardness, tensile) = steel(kind);
+    let mut material = Material::new(format!("Steel {hardness} HV"), MaterialType::Metal);
+    material.youngs_modulus = Some(205_000.0 * PA_PER_MPA);
+    material.poisson_ratio = Some(0.3);
+    material.density = Some(7850.0);
+    material.tensile_strength = Some(tensile * PA_PER_MPA);
+    material.thermal_expansion = Some(11.5e-6);
+    material
+}
+
+/// Standard washer for a metric nominal diameter (mm)
+///
+/// Spherical and conical DIN 6319 pairs depend on the seat and are entered by hand.
+pub fn standard(kind: WasherType, diameter: f64) -> Option<Washer> {
+    let table = match kind {
+        WasherType::Plain | WasherType::Hardened => ISO_7089,
+        WasherType::WedgeLock => WEDGE_LOCK,
+        WasherType::Spherical | WasherType::Conical => return None,
+    };
+    let &(_, id, od, thickness) = table.iter().find(|(d, ..)| (d - diameter).abs() < 1e-6)?;
+    Some(Washer {
+        kind,
+        id: id as f32,
+        od: od as f32,
+        thickness: thickness as f32,
+        material: material(kind),
+    })
+}
+
+/// Standard designation of a washer, for labels
+pub fn designation(kind: WasherType, diameter: f64) -> String {
+    match kind {
+        WasherType::Plain => format!("ISO 7089 - {diameter} - 200 HV"),
+        WasherType::Hardened => format!("ISO 7089 - {diameter} - 300 HV"),
+        WasherType::Spherical => format!("DIN 6319 C/D - {diameter}"),
+        WasherType::Conical => format!("DIN 6319 C/G - {diameter}"),
+        WasherType::WedgeLock => format!("Wedge locking pair M{diameter}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn looks_up_by_nominal_diameter() {
+        let washer = standard(WasherType::Hardened, 12.0).unwrap();
+        assert_eq!((washer.id, washer.od, washer.thickness), (13.0, 24.0, 2.5));
+        assert_eq!(washer.material.tensile_strength, Some(960e6));
+        assert!(standard(WasherType::Plain, 11.0).is_none());
+        assert!(standard(WasherType::Spherical, 12.0).is_none());
+    }
+}